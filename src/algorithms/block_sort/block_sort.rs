@@ -0,0 +1,295 @@
+use super::merge::{block_merge, merge_in_place, merge_with_buffer};
+use crate::algorithms::insertion_sort;
+use crate::Order;
+
+const INSERTION_THRESHOLD: usize = 16;
+const MIN_KEYS: usize = 8;
+
+pub fn sort<T: PartialOrd>(data: &mut [T], order: Order) {
+    let predicate = match order {
+        Order::Asc => |a: &T, b: &T| a < b,
+        Order::Desc => |a: &T, b: &T| a > b,
+    };
+    sort_by_predicate(data, predicate);
+}
+
+pub fn sort_by_predicate<T, P>(data: &mut [T], predicate: P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let len = data.len();
+    if len <= 2 * INSERTION_THRESHOLD {
+        insertion_sort::sort_by_predicate(data, &predicate);
+        return;
+    }
+
+    let mut block = 1;
+    while block * block < len {
+        block *= 2;
+    }
+    let tags = (len - 1) / block + 1;
+
+    // The front of the slice becomes a set of distinct keys: `tags` of them
+    // label the blocks during block merges and the next `block` ones serve as
+    // the merge buffer. With fewer distinct keys, half of them still label
+    // the blocks and the other half is a smaller buffer. Only a handful of
+    // distinct values are left to rotation based merges alone.
+    let keys = collect_keys(data, tags + block, &predicate);
+    if keys < MIN_KEYS {
+        sort_in_place(data, &predicate);
+        return;
+    }
+
+    if keys < tags + block {
+        sort_with_few_keys(data, keys, &predicate);
+    } else {
+        sort_with_keys(data, keys, tags, block, &predicate);
+    }
+
+    insertion_sort::sort_by_predicate(&mut data[..keys], &predicate);
+    merge_in_place(data, keys, &predicate);
+}
+
+fn sort_with_keys<T, P>(data: &mut [T], keys: usize, buffer: usize, block: usize, predicate: &P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let len = data.len();
+    for chunk in data[keys..].chunks_mut(INSERTION_THRESHOLD) {
+        insertion_sort::sort_by_predicate(chunk, predicate);
+    }
+
+    let mut width = INSERTION_THRESHOLD;
+    while width < len - keys {
+        for start in (keys..len).step_by(2 * width) {
+            let mid = (start + width).min(len);
+            let end = (start + 2 * width).min(len);
+            if mid == end {
+                continue;
+            }
+
+            if width <= block || end - mid <= block {
+                merge_with_buffer(data, start, mid, end, buffer, predicate);
+            } else {
+                block_merge(data, start, mid, end, Some(buffer), block, predicate);
+            }
+        }
+        width *= 2;
+    }
+}
+
+// Like `sort_with_keys`, but the keys are split into `keys / 2` tags and a
+// buffer of the rest. Runs too long for that buffer are block merged with as
+// many blocks as there are tags, merging neighbouring blocks with rotations
+// once a block no longer fits the buffer.
+fn sort_with_few_keys<T, P>(data: &mut [T], keys: usize, predicate: &P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let len = data.len();
+    let tags = keys / 2;
+    let buffer = keys - tags;
+    for chunk in data[keys..].chunks_mut(INSERTION_THRESHOLD) {
+        insertion_sort::sort_by_predicate(chunk, predicate);
+    }
+
+    let mut width = INSERTION_THRESHOLD;
+    while width < len - keys {
+        let mut block = 1;
+        while block * block < 2 * width || block * tags < 2 * width {
+            block *= 2;
+        }
+
+        for start in (keys..len).step_by(2 * width) {
+            let mid = (start + width).min(len);
+            let end = (start + 2 * width).min(len);
+            if mid == end {
+                continue;
+            }
+
+            if width <= buffer || end - mid <= buffer {
+                merge_with_buffer(data, start, mid, end, tags, predicate);
+            } else if end - mid <= block {
+                merge_in_place(&mut data[start..end], width, predicate);
+            } else if block <= buffer {
+                block_merge(data, start, mid, end, Some(tags), block, predicate);
+            } else {
+                block_merge(data, start, mid, end, None, block, predicate);
+            }
+        }
+        width *= 2;
+    }
+}
+
+fn sort_in_place<T, P>(data: &mut [T], predicate: &P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let len = data.len();
+    for chunk in data.chunks_mut(INSERTION_THRESHOLD) {
+        insertion_sort::sort_by_predicate(chunk, predicate);
+    }
+
+    let mut width = INSERTION_THRESHOLD;
+    while width < len {
+        for start in (0..len).step_by(2 * width) {
+            let mid = (start + width).min(len);
+            let end = (start + 2 * width).min(len);
+            merge_in_place(&mut data[start..end], mid - start, predicate);
+        }
+        width *= 2;
+    }
+}
+
+// Moves the first occurrences of up to `wanted` distinct values to the front
+// of the slice in sorted order, keeping every other element in its original
+// relative order, and returns how many were found.
+fn collect_keys<T, P>(data: &mut [T], wanted: usize, predicate: &P) -> usize
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let mut head = 0;
+    let mut found = 1;
+
+    for i in 1..data.len() {
+        if found == wanted {
+            break;
+        }
+
+        let pos = data[head..head + found].partition_point(|key| predicate(key, &data[i]));
+        if pos == found || predicate(&data[i], &data[head + pos]) {
+            data[head..i].rotate_left(found);
+            head = i - found;
+            data[head + pos..=i].rotate_right(1);
+            found += 1;
+        }
+    }
+
+    data[..head + found].rotate_left(head);
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{random_vec, Car, Person};
+
+    #[test]
+    fn sort_array_asc() {
+        let mut arr = [2, 3, 1, 5, 4];
+        sort(&mut arr, Order::Asc);
+        assert_eq!([1, 2, 3, 4, 5], arr);
+
+        let mut arr = [
+            5, 3, 8, 2, 1, 5, 7, 2, 6, 3, 0, 2, 4, 6, 8, 9, 9, 1, 5, 4, 3, 2, 4,
+        ];
+        sort(&mut arr, Order::Asc);
+        assert_eq!(
+            [0, 1, 1, 2, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 7, 8, 8, 9, 9],
+            arr
+        );
+    }
+
+    #[test]
+    fn sort_array_desc() {
+        let mut arr = [2, 3, 1, 5, 4];
+        sort(&mut arr, Order::Desc);
+        assert_eq!([5, 4, 3, 2, 1], arr);
+    }
+
+    #[test]
+    fn sort_random_vector() {
+        let mut expected = random_vec(5000, 29);
+        expected.sort();
+
+        let mut vec = random_vec(5000, 29);
+        sort(&mut vec, Order::Asc);
+        assert_eq!(expected, vec);
+    }
+
+    #[test]
+    fn sort_is_stable() {
+        let keys = random_vec(5000, 31);
+
+        for distinct in [3, 40, 1000] {
+            let mut expected: Vec<(u32, usize)> =
+                keys.iter().map(|k| k % distinct).zip(0..).collect();
+            expected.sort_by_key(|(k, _)| *k);
+
+            let mut vec: Vec<(u32, usize)> = keys.iter().map(|k| k % distinct).zip(0..).collect();
+            sort_by_predicate(&mut vec, |a, b| a.0 < b.0);
+            assert_eq!(expected, vec);
+        }
+    }
+
+    #[test]
+    fn sort_few_keys_is_n_log_n() {
+        use std::cell::Cell;
+
+        // 100k elements want 708 keys, so every count below takes the path
+        // that splits the keys it found into tags and a smaller buffer.
+        let len = 100_000;
+        let keys = random_vec(len, 37);
+
+        for distinct in [8, 100, 700] {
+            let mut expected: Vec<(u32, usize)> =
+                keys.iter().map(|k| k % distinct).zip(0..).collect();
+            expected.sort_by_key(|(k, _)| *k);
+
+            let comparisons = Cell::new(0);
+            let mut vec: Vec<(u32, usize)> = keys.iter().map(|k| k % distinct).zip(0..).collect();
+            sort_by_predicate(&mut vec, |a, b| {
+                comparisons.set(comparisons.get() + 1);
+                a.0 < b.0
+            });
+            assert_eq!(expected, vec);
+            assert!(comparisons.get() < 4 * len * 17);
+        }
+    }
+
+    #[test]
+    fn collect_keys_test() {
+        let mut arr = [(3, 'a'), (1, 'a'), (3, 'b'), (2, 'a'), (1, 'b'), (4, 'a')];
+        let keys = collect_keys(&mut arr, 3, &|a, b| a.0 < b.0);
+
+        assert_eq!(keys, 3);
+        assert_eq!(
+            arr,
+            [(1, 'a'), (2, 'a'), (3, 'a'), (3, 'b'), (1, 'b'), (4, 'a')]
+        );
+    }
+
+    #[test]
+    fn sort_struct_vector() {
+        let person1 = Person::new(25);
+        let person2 = Person::new(15);
+        let person3 = Person::new(35);
+
+        let mut vec = vec![person1, person2, person3];
+        sort(&mut vec, Order::Asc);
+        assert_eq!(vec![person2, person1, person3], vec);
+    }
+
+    #[test]
+    fn sort_array_by_predicate() {
+        let mut arr = [
+            5, 3, 8, 2, 1, 5, 7, 2, 6, 3, 0, 2, 4, 6, 8, 9, 9, 1, 5, 4, 3, 2, 4,
+        ];
+        sort_by_predicate(&mut arr, |a, b| a < b);
+        assert_eq!(
+            [0, 1, 1, 2, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 7, 8, 8, 9, 9],
+            arr
+        );
+    }
+
+    #[test]
+    fn sort_struct_vector_by_predicate() {
+        let car1 = Car { age: 25 };
+        let car2 = Car { age: 15 };
+        let car3 = Car { age: 35 };
+
+        let mut vec = vec![car1, car2, car3];
+        sort_by_predicate(&mut vec, |c1, c2| c1.age < c2.age);
+        assert_eq!(vec![car2, car1, car3], vec);
+    }
+}
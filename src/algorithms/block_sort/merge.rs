@@ -0,0 +1,355 @@
+use crate::algorithms::insertion_sort;
+
+// The buffered merges below borrow `buffer..buffer + n` as swap space. Its
+// elements are moved around while merging but always end up back inside the
+// buffer region, only in a different order.
+
+pub fn merge_with_buffer<T, P>(
+    data: &mut [T],
+    start: usize,
+    mid: usize,
+    end: usize,
+    buffer: usize,
+    predicate: &P,
+) where
+    P: Fn(&T, &T) -> bool,
+{
+    if start == mid || mid == end || !predicate(&data[mid], &data[mid - 1]) {
+        return;
+    }
+
+    if mid - start <= end - mid {
+        merge_lo(data, start, mid, end, buffer, predicate);
+    } else {
+        merge_hi(data, start, mid, end, buffer, predicate);
+    }
+}
+
+fn merge_lo<T, P>(
+    data: &mut [T],
+    start: usize,
+    mid: usize,
+    end: usize,
+    buffer: usize,
+    predicate: &P,
+) where
+    P: Fn(&T, &T) -> bool,
+{
+    let len = mid - start;
+    swap_ranges(data, start, buffer, len);
+
+    let (mut i, mut j, mut k) = (buffer, mid, start);
+    while i < buffer + len && j < end {
+        if predicate(&data[j], &data[i]) {
+            data.swap(k, j);
+            j += 1;
+        } else {
+            data.swap(k, i);
+            i += 1;
+        }
+        k += 1;
+    }
+    swap_ranges(data, i, k, buffer + len - i);
+}
+
+fn merge_hi<T, P>(
+    data: &mut [T],
+    start: usize,
+    mid: usize,
+    end: usize,
+    buffer: usize,
+    predicate: &P,
+) where
+    P: Fn(&T, &T) -> bool,
+{
+    let len = end - mid;
+    swap_ranges(data, mid, buffer, len);
+
+    let (mut i, mut j, mut k) = (buffer + len, mid, end);
+    while i > buffer && j > start {
+        if predicate(&data[i - 1], &data[j - 1]) {
+            data.swap(k - 1, j - 1);
+            j -= 1;
+        } else {
+            data.swap(k - 1, i - 1);
+            i -= 1;
+        }
+        k -= 1;
+    }
+    swap_ranges(data, buffer, k - (i - buffer), i - buffer);
+}
+
+pub fn merge_in_place<T, P>(data: &mut [T], mid: usize, predicate: &P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let (mut start, mut mid, mut end) = (0, mid, data.len());
+
+    if mid - start <= end - mid {
+        while start < mid && mid < end {
+            let head = &data[start];
+            let count = data[mid..end].partition_point(|x| predicate(x, head));
+            if count > 0 {
+                data[start..mid + count].rotate_left(mid - start);
+            }
+            start += count + 1;
+            mid += count;
+
+            while start < mid && mid < end && !predicate(&data[mid], &data[start]) {
+                start += 1;
+            }
+        }
+    } else {
+        while start < mid && mid < end {
+            let last = &data[end - 1];
+            let count = mid - start - data[start..mid].partition_point(|x| !predicate(last, x));
+            if count > 0 {
+                data[mid - count..end].rotate_left(count);
+            }
+            end -= count + 1;
+            mid -= count;
+
+            while start < mid && mid < end && !predicate(&data[end - 1], &data[mid - 1]) {
+                end -= 1;
+            }
+        }
+    }
+}
+
+// Merges two sorted runs that are both longer than a block. The first
+// `(end - start) / block` elements of `data` are distinct tags that record
+// which run every block came from, which keeps the merge stable. Without a
+// buffer of at least `block` elements the blocks are merged with rotations.
+pub fn block_merge<T, P>(
+    data: &mut [T],
+    start: usize,
+    mid: usize,
+    end: usize,
+    buffer: Option<usize>,
+    block: usize,
+    predicate: &P,
+) where
+    P: Fn(&T, &T) -> bool,
+{
+    if !predicate(&data[mid], &data[mid - 1]) {
+        return;
+    }
+
+    let blocks = (end - start) / block;
+    let tail = (end - start) % block;
+    let tail_start = end - tail;
+
+    insertion_sort::sort_by_predicate(&mut data[..blocks], predicate);
+    let mut midkey = (mid - start) / block;
+
+    // Selection sort the blocks by their first element, breaking ties by
+    // tag so that blocks of the first run stay ahead of the second one.
+    for u in 0..blocks - 1 {
+        let mut min = u;
+        for v in u + 1..blocks {
+            let (head_v, head_min) = (&data[start + v * block], &data[start + min * block]);
+            if predicate(head_v, head_min)
+                || (!predicate(head_min, head_v) && predicate(&data[v], &data[min]))
+            {
+                min = v;
+            }
+        }
+        if min != u {
+            swap_ranges(data, start + u * block, start + min * block, block);
+            data.swap(u, min);
+            if midkey == u || midkey == min {
+                midkey ^= u ^ min;
+            }
+        }
+    }
+
+    // Blocks of the first run at the very end that start after the partial
+    // tail block have to be merged with it separately.
+    let mut trailing = 0;
+    while trailing < blocks
+        && tail > 0
+        && predicate(
+            &data[tail_start],
+            &data[tail_start - (trailing + 1) * block],
+        )
+    {
+        trailing += 1;
+    }
+    let blocks = blocks - trailing;
+
+    let from_first = |data: &[T], i: usize| predicate(&data[i], &data[midkey]);
+
+    let mut rest_start = start;
+    let mut rest_len = if blocks > 0 { block } else { 0 };
+    let mut rest_first = blocks == 0 || from_first(data, 0);
+
+    for c in 1..blocks {
+        let next_first = from_first(data, c);
+        if next_first == rest_first {
+            rest_start = start + c * block;
+            rest_len = block;
+        } else {
+            (rest_start, rest_len, rest_first) = match buffer {
+                Some(buffer) => merge_rest(
+                    data, rest_start, rest_len, rest_first, block, buffer, predicate,
+                ),
+                None => {
+                    merge_rest_in_place(data, rest_start, rest_len, rest_first, block, predicate)
+                }
+            };
+        }
+    }
+
+    if tail > 0 {
+        if rest_first {
+            rest_len += trailing * block;
+        } else {
+            rest_start = start + blocks * block;
+            rest_len = trailing * block;
+        }
+        match buffer {
+            Some(buffer) => merge_with_buffer(
+                data,
+                rest_start,
+                rest_start + rest_len,
+                end,
+                buffer,
+                predicate,
+            ),
+            None => merge_in_place(&mut data[rest_start..end], rest_len, predicate),
+        }
+    }
+}
+
+// Merges the unfinished rest of one run with the next block of the other run
+// until either of them is used up. Whatever is left over becomes the new rest
+// and is moved to the end of the block.
+fn merge_rest<T, P>(
+    data: &mut [T],
+    rest_start: usize,
+    rest_len: usize,
+    rest_first: bool,
+    block: usize,
+    buffer: usize,
+    predicate: &P,
+) -> (usize, usize, bool)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    swap_ranges(data, rest_start, buffer, rest_len);
+
+    let end = rest_start + rest_len + block;
+    let (mut i, mut j, mut k) = (buffer, rest_start + rest_len, rest_start);
+    while i < buffer + rest_len && j < end {
+        let take_rest = if rest_first {
+            !predicate(&data[j], &data[i])
+        } else {
+            predicate(&data[i], &data[j])
+        };
+        if take_rest {
+            data.swap(k, i);
+            i += 1;
+        } else {
+            data.swap(k, j);
+            j += 1;
+        }
+        k += 1;
+    }
+
+    if i < buffer + rest_len {
+        let left = buffer + rest_len - i;
+        swap_ranges(data, i, k, left);
+        (k, left, rest_first)
+    } else {
+        (j, end - j, !rest_first)
+    }
+}
+
+// Same as `merge_rest` with rotations instead of a buffer. Every rotation
+// moves the rest past the elements of the block that precede its head, so
+// the cost is bounded by the rest times the number of times the two
+// interleave, which summed over a whole block merge is about the number of
+// distinct values plus the number of blocks.
+fn merge_rest_in_place<T, P>(
+    data: &mut [T],
+    rest_start: usize,
+    rest_len: usize,
+    rest_first: bool,
+    block: usize,
+    predicate: &P,
+) -> (usize, usize, bool)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let end = rest_start + rest_len + block;
+    let (mut i, mut j) = (rest_start, rest_start + rest_len);
+
+    while i < j {
+        let head = &data[i];
+        let count = data[j..end].partition_point(|x| {
+            if rest_first {
+                predicate(x, head)
+            } else {
+                !predicate(head, x)
+            }
+        });
+        if count > 0 {
+            data[i..j + count].rotate_left(j - i);
+            i += count;
+            j += count;
+        }
+        if j == end {
+            return (i, end - i, rest_first);
+        }
+
+        while i < j
+            && if rest_first {
+                !predicate(&data[j], &data[i])
+            } else {
+                predicate(&data[i], &data[j])
+            }
+        {
+            i += 1;
+        }
+    }
+    (j, end - j, !rest_first)
+}
+
+fn swap_ranges<T>(data: &mut [T], a: usize, b: usize, len: usize) {
+    for t in 0..len {
+        data.swap(a + t, b + t);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_with_buffer_test() {
+        let mut arr = [0, 0, 0, 1, 4, 6, 2, 3, 5, 7];
+        merge_with_buffer(&mut arr, 3, 6, 10, 0, &|a, b| a < b);
+        assert_eq!(arr[3..], [1, 2, 3, 4, 5, 6, 7]);
+
+        let mut arr = [0, 0, 0, 1, 4, 6, 8, 2, 9];
+        merge_with_buffer(&mut arr, 3, 7, 9, 0, &|a, b| a < b);
+        assert_eq!(arr[3..], [1, 2, 4, 6, 8, 9]);
+    }
+
+    #[test]
+    fn merge_in_place_test() {
+        let mut arr = [(1, 'a'), (3, 'a'), (1, 'b'), (2, 'b'), (3, 'b'), (4, 'b')];
+        merge_in_place(&mut arr, 2, &|a, b| a.0 < b.0);
+        assert_eq!(
+            arr,
+            [(1, 'a'), (1, 'b'), (2, 'b'), (3, 'a'), (3, 'b'), (4, 'b')]
+        );
+
+        let mut arr = [(1, 'a'), (2, 'a'), (3, 'a'), (4, 'a'), (2, 'b'), (3, 'b')];
+        merge_in_place(&mut arr, 4, &|a, b| a.0 < b.0);
+        assert_eq!(
+            arr,
+            [(1, 'a'), (2, 'a'), (2, 'b'), (3, 'a'), (3, 'b'), (4, 'a')]
+        );
+    }
+}
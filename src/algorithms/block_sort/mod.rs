@@ -0,0 +1,4 @@
+mod block_sort;
+mod merge;
+
+pub use block_sort::*;
@@ -1,31 +1,111 @@
 pub fn create_heap_by_predicate<T, P>(data: &mut [T], predicate: &P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    create_d_heap_by_predicate::<2, T, P>(data, predicate)
+}
+
+pub fn down_heap_by_predicate<T, P>(data: &mut [T], i: usize, l: usize, predicate: &P) -> usize
+where
+    P: Fn(&T, &T) -> bool,
+{
+    down_d_heap_by_predicate::<2, T, P>(data, i, l, predicate)
+}
+
+pub fn up_heap_by_predicate<T, P>(data: &mut [T], mut i: usize, predicate: &P) -> usize
+where
+    P: Fn(&T, &T) -> bool,
+{
+    while i > 0 {
+        let parent = (i - 1) / 2;
+        if !predicate(&data[parent], &data[i]) {
+            break;
+        }
+        data.swap(parent, i);
+        i = parent;
+    }
+    i
+}
+
+pub fn create_d_heap_by_predicate<const D: usize, T, P>(data: &mut [T], predicate: &P)
 where
     P: Fn(&T, &T) -> bool,
 {
     let l = data.len();
-    for i in (0..l / 2).rev() {
-        down_heap_by_predicate(data, i, l, predicate)
+    if l < 2 {
+        return;
+    }
+
+    for i in (0..=(l - 2) / D).rev() {
+        down_d_heap_by_predicate::<D, T, P>(data, i, l, predicate);
     }
 }
 
-pub fn down_heap_by_predicate<T, P>(data: &mut [T], i: usize, l: usize, predicate: &P)
+pub fn down_d_heap_by_predicate<const D: usize, T, P>(
+    data: &mut [T],
+    i: usize,
+    l: usize,
+    predicate: &P,
+) -> usize
 where
     P: Fn(&T, &T) -> bool,
 {
     let mut max = i;
-    let left = 2 * i + 1;
-    let right = 2 * i + 2;
+    let first = D * i + 1;
 
-    if left < l && !predicate(&data[left], &data[max]) {
-        max = left;
+    for child in first..(first + D).min(l) {
+        if !predicate(&data[child], &data[max]) {
+            max = child;
+        }
     }
-    if right < l && !predicate(&data[right], &data[max]) {
-        max = right;
+
+    if max == i {
+        return i;
     }
 
-    if max != i {
-        data.swap(max, i);
-        down_heap_by_predicate(data, max, l, predicate)
+    data.swap(max, i);
+    down_d_heap_by_predicate::<D, T, P>(data, max, l, predicate)
+}
+
+pub fn create_heap_bottom_up_by_predicate<T, P>(data: &mut [T], predicate: &P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let l = data.len();
+    for i in (0..l / 2).rev() {
+        down_heap_bottom_up_by_predicate(data, i, l, predicate)
+    }
+}
+
+pub fn down_heap_bottom_up_by_predicate<T, P>(data: &mut [T], i: usize, l: usize, predicate: &P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let mut j = i;
+    loop {
+        let left = 2 * j + 1;
+        let right = 2 * j + 2;
+        if right < l {
+            j = if predicate(&data[left], &data[right]) {
+                right
+            } else {
+                left
+            };
+        } else {
+            if left < l {
+                j = left;
+            }
+            break;
+        }
+    }
+
+    while j > i && predicate(&data[j], &data[i]) {
+        j = (j - 1) / 2;
+    }
+
+    while j > i {
+        data.swap(i, j);
+        j = (j - 1) / 2;
     }
 }
 
@@ -39,4 +119,29 @@ mod tests {
         create_heap_by_predicate(&mut arr, &|a, b| a < b);
         assert_eq!(arr, [7, 6, 4, 2, 5, 3, 1, 0, 2]);
     }
+
+    #[test]
+    fn up_heap_by_predicate_test() {
+        let mut arr = [7, 6, 4, 2, 5, 3, 1, 0, 2, 8];
+        assert_eq!(up_heap_by_predicate(&mut arr, 9, &|a, b| a < b), 0);
+        assert_eq!(arr, [8, 7, 4, 2, 6, 3, 1, 0, 2, 5]);
+    }
+
+    #[test]
+    fn create_d_heap_by_predicate_test() {
+        let mut arr = [3, 2, 4, 2, 5, 7, 1, 0, 6, 8, 3];
+        create_d_heap_by_predicate::<4, _, _>(&mut arr, &|a, b| a < b);
+
+        assert_eq!(arr[0], 8);
+        for i in 1..arr.len() {
+            assert!(arr[(i - 1) / 4] >= arr[i]);
+        }
+    }
+
+    #[test]
+    fn create_heap_bottom_up_by_predicate_test() {
+        let mut arr = [3, 2, 4, 2, 5, 7, 1, 0, 6];
+        create_heap_bottom_up_by_predicate(&mut arr, &|a, b| a < b);
+        assert_eq!(arr, [7, 6, 4, 2, 5, 3, 1, 0, 2]);
+    }
 }
@@ -1,43 +1,96 @@
-use crate::Order;
+use crate::{Arity, Heap, Order};
 
-use super::heap::{create_heap_by_predicate, down_heap_by_predicate};
+use super::heap::{
+    create_d_heap_by_predicate, create_heap_bottom_up_by_predicate, create_heap_by_predicate,
+    down_d_heap_by_predicate, down_heap_bottom_up_by_predicate, down_heap_by_predicate,
+};
+use super::weak_heap;
 
-pub fn sort<T: PartialOrd>(data: &mut [T], order: Order) {
+pub fn sort<T: PartialOrd>(data: &mut [T], order: Order, heap: Heap) {
     let predicate = match order {
         Order::Asc => |a: &T, b: &T| a < b,
         Order::Desc => |a: &T, b: &T| a > b,
     };
-    sort_by_predicate(data, predicate);
+    sort_by_predicate(data, predicate, heap);
 }
 
-pub fn sort_by_predicate<T, P>(data: &mut [T], predicate: P)
+pub fn sort_by_predicate<T, P>(data: &mut [T], predicate: P, heap: Heap)
 where
     P: Fn(&T, &T) -> bool,
 {
-    create_heap_by_predicate(data, &predicate);
+    match heap {
+        Heap::Standard => {
+            create_heap_by_predicate(data, &predicate);
+
+            let len = data.len();
+            for i in (1..len).rev() {
+                data.swap(0, i);
+                down_heap_by_predicate(data, 0, i, &predicate);
+            }
+        }
+        Heap::BottomUp => {
+            create_heap_bottom_up_by_predicate(data, &predicate);
+
+            let len = data.len();
+            for i in (1..len).rev() {
+                data.swap(0, i);
+                down_heap_bottom_up_by_predicate(data, 0, i, &predicate)
+            }
+        }
+        Heap::Weak => weak_heap::sort_by_predicate(data, &predicate),
+    }
+}
+
+pub fn sort_with_arity<T: PartialOrd>(data: &mut [T], order: Order, arity: Arity) {
+    let predicate = match order {
+        Order::Asc => |a: &T, b: &T| a < b,
+        Order::Desc => |a: &T, b: &T| a > b,
+    };
+    sort_by_predicate_with_arity(data, predicate, arity);
+}
+
+pub fn sort_by_predicate_with_arity<T, P>(data: &mut [T], predicate: P, arity: Arity)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    match arity {
+        Arity::Binary => sort_d_ary::<2, T, P>(data, &predicate),
+        Arity::Ternary => sort_d_ary::<3, T, P>(data, &predicate),
+        Arity::Quaternary => sort_d_ary::<4, T, P>(data, &predicate),
+        Arity::Octonary => sort_d_ary::<8, T, P>(data, &predicate),
+    }
+}
+
+fn sort_d_ary<const D: usize, T, P>(data: &mut [T], predicate: &P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    create_d_heap_by_predicate::<D, T, P>(data, predicate);
 
     let len = data.len();
     for i in (1..len).rev() {
         data.swap(0, i);
-        down_heap_by_predicate(data, 0, i, &predicate)
+        down_d_heap_by_predicate::<D, T, P>(data, 0, i, predicate);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::helpers::{Car, Person};
+    use std::cell::Cell;
+
+    use crate::helpers::{random_vec, Car, Person};
 
     #[test]
     fn sort_array_asc() {
         let mut arr = [2, 3, 1, 5, 4];
-        sort(&mut arr, Order::Asc);
+        sort(&mut arr, Order::Asc, Heap::Standard);
         assert_eq!([1, 2, 3, 4, 5], arr);
 
         let mut arr = [
             5, 3, 8, 2, 1, 5, 7, 2, 6, 3, 0, 2, 4, 6, 8, 9, 9, 1, 5, 4, 3, 2, 4,
         ];
-        sort(&mut arr, Order::Asc);
+        sort(&mut arr, Order::Asc, Heap::Standard);
         assert_eq!(
             [0, 1, 1, 2, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 7, 8, 8, 9, 9],
             arr
@@ -47,10 +100,70 @@ mod tests {
     #[test]
     fn sort_array_desc() {
         let mut arr = [2, 3, 1, 5, 4];
-        sort(&mut arr, Order::Desc);
+        sort(&mut arr, Order::Desc, Heap::Standard);
+        assert_eq!([5, 4, 3, 2, 1], arr);
+    }
+
+    #[test]
+    fn sort_strategies() {
+        let mut expected = random_vec(1000, 41);
+        expected.sort();
+
+        for heap in [Heap::Standard, Heap::BottomUp, Heap::Weak] {
+            let mut vec = random_vec(1000, 41);
+            sort(&mut vec, Order::Asc, heap);
+            assert_eq!(expected, vec);
+        }
+
+        for heap in [Heap::BottomUp, Heap::Weak] {
+            let mut arr = [2, 3, 1, 5, 4];
+            sort(&mut arr, Order::Desc, heap);
+            assert_eq!([5, 4, 3, 2, 1], arr);
+        }
+    }
+
+    #[test]
+    fn sort_arities() {
+        let mut expected = random_vec(1000, 47);
+        expected.sort();
+
+        for arity in [
+            Arity::Binary,
+            Arity::Ternary,
+            Arity::Quaternary,
+            Arity::Octonary,
+        ] {
+            let mut vec = random_vec(1000, 47);
+            sort_with_arity(&mut vec, Order::Asc, arity);
+            assert_eq!(expected, vec);
+        }
+
+        let mut arr = [2, 3, 1, 5, 4];
+        sort_by_predicate_with_arity(&mut arr, |a, b| a > b, Arity::Ternary);
         assert_eq!([5, 4, 3, 2, 1], arr);
     }
 
+    #[test]
+    fn sort_strategies_save_comparisons() {
+        let count = |heap| {
+            let comparisons = Cell::new(0);
+            let mut vec = random_vec(10_000, 43);
+            sort_by_predicate(
+                &mut vec,
+                |a, b| {
+                    comparisons.set(comparisons.get() + 1);
+                    a < b
+                },
+                heap,
+            );
+            comparisons.get()
+        };
+
+        let standard = count(Heap::Standard);
+        assert!(count(Heap::BottomUp) < standard);
+        assert!(count(Heap::Weak) < standard);
+    }
+
     #[test]
     fn sort_struct_vector() {
         let person1 = Person::new(25);
@@ -58,7 +171,7 @@ mod tests {
         let person3 = Person::new(35);
 
         let mut vec = vec![person1, person2, person3];
-        sort(&mut vec, Order::Asc);
+        sort(&mut vec, Order::Asc, Heap::Standard);
         assert_eq!(vec![person2, person1, person3], vec);
     }
 
@@ -67,7 +180,7 @@ mod tests {
         let mut arr = [
             5, 3, 8, 2, 1, 5, 7, 2, 6, 3, 0, 2, 4, 6, 8, 9, 9, 1, 5, 4, 3, 2, 4,
         ];
-        sort_by_predicate(&mut arr, |a, b| a < b);
+        sort_by_predicate(&mut arr, |a, b| a < b, Heap::Standard);
         assert_eq!(
             [0, 1, 1, 2, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 7, 8, 8, 9, 9],
             arr
@@ -81,7 +194,7 @@ mod tests {
         let car3 = Car { age: 35 };
 
         let mut vec = vec![car1, car2, car3];
-        sort_by_predicate(&mut vec, |c1, c2| c1.age < c2.age);
+        sort_by_predicate(&mut vec, |c1, c2| c1.age < c2.age, Heap::Standard);
         assert_eq!(vec![car2, car1, car3], vec);
     }
 }
@@ -1,4 +1,9 @@
-mod heap;
+pub(crate) mod heap;
 mod heap_sort;
+mod slice_heap;
+mod sorted_iter;
+mod weak_heap;
 
 pub use heap_sort::*;
+pub use slice_heap::*;
+pub use sorted_iter::*;
@@ -0,0 +1,159 @@
+use crate::Order;
+
+use super::heap::{create_heap_by_predicate, down_heap_by_predicate, up_heap_by_predicate};
+
+// Heaps built here are max-heaps with respect to the predicate, like the
+// ones heap_sort uses, so `Order::Asc` keeps the greatest element at the
+// front and `sort_heap` leaves the slice in ascending order.
+
+pub fn make_heap<T: PartialOrd>(data: &mut [T], order: Order) {
+    make_heap_by_predicate(data, predicate(order));
+}
+
+pub fn make_heap_by_predicate<T, P>(data: &mut [T], predicate: P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    create_heap_by_predicate(data, &predicate);
+}
+
+// Expects `data[..len - 1]` to be a heap and adds the last element to it.
+pub fn push_heap<T: PartialOrd>(data: &mut [T], order: Order) {
+    push_heap_by_predicate(data, predicate(order));
+}
+
+pub fn push_heap_by_predicate<T, P>(data: &mut [T], predicate: P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    if let Some(last) = data.len().checked_sub(1) {
+        up_heap_by_predicate(data, last, &predicate);
+    }
+}
+
+// Moves the front of the heap to the end of the slice and restores the heap
+// on `data[..len - 1]`.
+pub fn pop_heap<T: PartialOrd>(data: &mut [T], order: Order) {
+    pop_heap_by_predicate(data, predicate(order));
+}
+
+pub fn pop_heap_by_predicate<T, P>(data: &mut [T], predicate: P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    if let Some(last) = data.len().checked_sub(1) {
+        data.swap(0, last);
+        down_heap_by_predicate(data, 0, last, &predicate);
+    }
+}
+
+pub fn sort_heap<T: PartialOrd>(data: &mut [T], order: Order) {
+    sort_heap_by_predicate(data, predicate(order));
+}
+
+pub fn sort_heap_by_predicate<T, P>(data: &mut [T], predicate: P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    for i in (1..data.len()).rev() {
+        data.swap(0, i);
+        down_heap_by_predicate(data, 0, i, &predicate);
+    }
+}
+
+pub fn is_heap<T: PartialOrd>(data: &[T], order: Order) -> bool {
+    is_heap_by_predicate(data, predicate(order))
+}
+
+pub fn is_heap_by_predicate<T, P>(data: &[T], predicate: P) -> bool
+where
+    P: Fn(&T, &T) -> bool,
+{
+    is_heap_until_by_predicate(data, predicate) == data.len()
+}
+
+// Returns the length of the longest prefix of `data` that is a heap.
+pub fn is_heap_until<T: PartialOrd>(data: &[T], order: Order) -> usize {
+    is_heap_until_by_predicate(data, predicate(order))
+}
+
+pub fn is_heap_until_by_predicate<T, P>(data: &[T], predicate: P) -> usize
+where
+    P: Fn(&T, &T) -> bool,
+{
+    (1..data.len())
+        .find(|&i| predicate(&data[(i - 1) / 2], &data[i]))
+        .unwrap_or(data.len())
+}
+
+fn predicate<T: PartialOrd>(order: Order) -> fn(&T, &T) -> bool {
+    match order {
+        Order::Asc => |a, b| a < b,
+        Order::Desc => |a, b| a > b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{random_vec, Car};
+
+    #[test]
+    fn make_and_sort_heap() {
+        let mut expected = random_vec(1000, 89);
+        expected.sort();
+
+        let mut vec = random_vec(1000, 89);
+        assert!(!is_heap(&vec, Order::Asc));
+        make_heap(&mut vec, Order::Asc);
+        assert!(is_heap(&vec, Order::Asc));
+        assert_eq!(vec[0], expected[999]);
+
+        sort_heap(&mut vec, Order::Asc);
+        assert_eq!(expected, vec);
+    }
+
+    #[test]
+    fn push_and_pop_heap() {
+        let mut vec = Vec::new();
+        for item in [3, 2, 4, 2, 5, 7, 1, 0, 6] {
+            vec.push(item);
+            push_heap(&mut vec, Order::Desc);
+            assert!(is_heap(&vec, Order::Desc));
+        }
+        assert_eq!(vec[0], 0);
+
+        let mut popped = Vec::new();
+        while !vec.is_empty() {
+            pop_heap(&mut vec, Order::Desc);
+            popped.push(vec.pop().unwrap());
+        }
+        assert_eq!(popped, [0, 1, 2, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn is_heap_until_test() {
+        assert_eq!(is_heap_until::<i32>(&[], Order::Asc), 0);
+        assert_eq!(is_heap_until(&[9, 5, 4, 1, 1, 3, 2, 6], Order::Asc), 7);
+        assert_eq!(is_heap_until(&[9, 5, 4, 1, 1, 3, 2, 6], Order::Desc), 1);
+        assert!(is_heap(&[1, 1, 1], Order::Desc));
+    }
+
+    #[test]
+    fn heap_by_predicate() {
+        let mut vec = vec![Car { age: 25 }, Car { age: 15 }, Car { age: 35 }];
+        make_heap_by_predicate(&mut vec, |c1, c2| c1.age < c2.age);
+        assert!(is_heap_by_predicate(&vec, |c1, c2| c1.age < c2.age));
+        assert_eq!(
+            is_heap_until_by_predicate(&vec, |c1, c2| c1.age > c2.age),
+            1
+        );
+
+        pop_heap_by_predicate(&mut vec, |c1, c2| c1.age < c2.age);
+        assert_eq!(vec[2], Car { age: 35 });
+
+        push_heap_by_predicate(&mut vec, |c1, c2| c1.age < c2.age);
+        sort_heap_by_predicate(&mut vec, |c1, c2| c1.age < c2.age);
+        assert_eq!(vec, [Car { age: 15 }, Car { age: 25 }, Car { age: 35 }]);
+    }
+}
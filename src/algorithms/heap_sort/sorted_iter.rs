@@ -0,0 +1,118 @@
+use std::mem;
+
+use crate::Order;
+
+use super::heap::{create_heap_by_predicate, down_heap_by_predicate};
+
+// Yields the elements of the slice in sorted order. The slice is heapified
+// up front and every step pops one element, which is moved behind the heap,
+// so after a full iteration the slice is sorted in the opposite order.
+pub struct SortedIter<'a, T, P>
+where
+    P: Fn(&T, &T) -> bool,
+{
+    heap: &'a mut [T],
+    predicate: P,
+}
+
+pub fn sorted_iter<T: PartialOrd>(
+    data: &mut [T],
+    order: Order,
+) -> SortedIter<'_, T, fn(&T, &T) -> bool> {
+    let predicate: fn(&T, &T) -> bool = match order {
+        Order::Asc => |a, b| a < b,
+        Order::Desc => |a, b| a > b,
+    };
+    sorted_iter_by_predicate(data, predicate)
+}
+
+pub fn sorted_iter_by_predicate<T, P>(data: &mut [T], predicate: P) -> SortedIter<'_, T, P>
+where
+    P: Fn(&T, &T) -> bool,
+{
+    // The heap keeps its greatest element at the root, so it is built with
+    // the reversed predicate to pop the least one first.
+    create_heap_by_predicate(data, &|a, b| predicate(b, a));
+    SortedIter {
+        heap: data,
+        predicate,
+    }
+}
+
+impl<'a, T, P> Iterator for SortedIter<'a, T, P>
+where
+    P: Fn(&T, &T) -> bool,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let last = self.heap.len().checked_sub(1)?;
+        let (heap, popped) = mem::take(&mut self.heap).split_at_mut(last);
+
+        if let Some(root) = heap.first_mut() {
+            mem::swap(root, &mut popped[0]);
+            let predicate = &self.predicate;
+            down_heap_by_predicate(heap, 0, last, &|a, b| predicate(b, a));
+        }
+
+        self.heap = heap;
+        Some(&popped[0])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl<T, P> ExactSizeIterator for SortedIter<'_, T, P> where P: Fn(&T, &T) -> bool {}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::helpers::{random_vec, Car};
+
+    #[test]
+    fn sorted_iter_asc_and_desc() {
+        let mut arr = [3, 2, 4, 2, 5, 7, 1, 0, 6];
+        let sorted: Vec<i32> = sorted_iter(&mut arr, Order::Asc).copied().collect();
+        assert_eq!(sorted, [0, 1, 2, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(arr, [7, 6, 5, 4, 3, 2, 2, 1, 0]);
+
+        let sorted: Vec<i32> = sorted_iter(&mut arr, Order::Desc).copied().collect();
+        assert_eq!(sorted, [7, 6, 5, 4, 3, 2, 2, 1, 0]);
+
+        assert_eq!(sorted_iter::<i32>(&mut [], Order::Asc).next(), None);
+    }
+
+    #[test]
+    fn sorted_iter_first_page() {
+        let mut expected = random_vec(10_000, 97);
+        expected.sort();
+
+        let mut vec = random_vec(10_000, 97);
+        let comparisons = Cell::new(0);
+        let iter = sorted_iter_by_predicate(&mut vec, |a, b| {
+            comparisons.set(comparisons.get() + 1);
+            a < b
+        });
+        assert_eq!(iter.len(), 10_000);
+
+        let page: Vec<u32> = iter.take(20).copied().collect();
+        assert_eq!(page, expected[..20]);
+        assert!(comparisons.get() < 3 * 10_000);
+    }
+
+    #[test]
+    fn sorted_iter_by_predicate_test() {
+        let mut vec = vec![Car { age: 25 }, Car { age: 15 }, Car { age: 35 }];
+        let mut iter = sorted_iter_by_predicate(&mut vec, |c1, c2| c1.age > c2.age);
+
+        assert_eq!(iter.next(), Some(&Car { age: 35 }));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next(), Some(&Car { age: 25 }));
+        assert_eq!(iter.next(), Some(&Car { age: 15 }));
+        assert_eq!(iter.next(), None);
+    }
+}
@@ -0,0 +1,61 @@
+pub fn sort_by_predicate<T, P>(data: &mut [T], predicate: &P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let len = data.len();
+    if len < 2 {
+        return;
+    }
+
+    // reverse[i] swaps the roles of the left and right child of node i, so
+    // children of i are 2i + reverse[i] and 2i + 1 - reverse[i].
+    let mut reverse = vec![false; len];
+
+    for j in (1..len).rev() {
+        let i = distinguished_ancestor(j, &reverse);
+        join(data, i, j, &mut reverse, predicate);
+    }
+
+    for end in (2..len).rev() {
+        data.swap(0, end);
+
+        let mut x = 1;
+        while 2 * x + (reverse[x] as usize) < end {
+            x = 2 * x + reverse[x] as usize;
+        }
+        while x > 0 {
+            join(data, 0, x, &mut reverse, predicate);
+            x /= 2;
+        }
+    }
+    data.swap(0, 1);
+}
+
+fn distinguished_ancestor(mut j: usize, reverse: &[bool]) -> usize {
+    while (j & 1 == 1) == reverse[j / 2] {
+        j /= 2;
+    }
+    j / 2
+}
+
+fn join<T, P>(data: &mut [T], i: usize, j: usize, reverse: &mut [bool], predicate: &P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    if predicate(&data[i], &data[j]) {
+        data.swap(i, j);
+        reverse[j] = !reverse[j];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_by_predicate_test() {
+        let mut arr = [3, 2, 4, 2, 5, 7, 1, 0, 6];
+        sort_by_predicate(&mut arr, &|a, b| a < b);
+        assert_eq!(arr, [0, 1, 2, 2, 3, 4, 5, 6, 7]);
+    }
+}
@@ -0,0 +1,52 @@
+use std::mem;
+
+pub fn merge_by_predicate<T, P>(data: &mut [T], mid: usize, predicate: &P, buffer: &mut Vec<T>)
+where
+    T: Clone,
+    P: Fn(&T, &T) -> bool,
+{
+    if mid == 0 || mid == data.len() || !predicate(&data[mid], &data[mid - 1]) {
+        return;
+    }
+
+    buffer.clear();
+    buffer.extend_from_slice(&data[..mid]);
+
+    // Everything in data[k..j] has already been copied out, so taking the
+    // next element from either run only has to swap it into position k.
+    let (mut i, mut j, mut k) = (0, mid, 0);
+    while i < buffer.len() && j < data.len() {
+        if predicate(&data[j], &buffer[i]) {
+            data.swap(k, j);
+            j += 1;
+        } else {
+            mem::swap(&mut data[k], &mut buffer[i]);
+            i += 1;
+        }
+        k += 1;
+    }
+    while i < buffer.len() {
+        mem::swap(&mut data[k], &mut buffer[i]);
+        i += 1;
+        k += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_by_predicate_test() {
+        let mut arr = [1, 4, 6, 9, 2, 3, 6, 10];
+        merge_by_predicate(&mut arr, 4, &|a, b| a < b, &mut Vec::new());
+        assert_eq!(arr, [1, 2, 3, 4, 6, 6, 9, 10]);
+    }
+
+    #[test]
+    fn merge_is_stable() {
+        let mut arr = [(1, 'a'), (2, 'a'), (1, 'b'), (2, 'b')];
+        merge_by_predicate(&mut arr, 2, &|a, b| a.0 < b.0, &mut Vec::new());
+        assert_eq!(arr, [(1, 'a'), (1, 'b'), (2, 'a'), (2, 'b')]);
+    }
+}
@@ -0,0 +1,184 @@
+use super::merge::merge_by_predicate;
+use crate::{Merge, Order};
+
+pub fn sort<T: PartialOrd + Clone>(data: &mut [T], order: Order, merge: Merge) {
+    sort_with_buffer(data, order, merge, &mut Vec::new());
+}
+
+pub fn sort_by_predicate<T, P>(data: &mut [T], predicate: P, merge: Merge)
+where
+    T: Clone,
+    P: Fn(&T, &T) -> bool,
+{
+    sort_by_predicate_with_buffer(data, predicate, merge, &mut Vec::new());
+}
+
+pub fn sort_with_buffer<T: PartialOrd + Clone>(
+    data: &mut [T],
+    order: Order,
+    merge: Merge,
+    buffer: &mut Vec<T>,
+) {
+    let predicate = match order {
+        Order::Asc => |a: &T, b: &T| a < b,
+        Order::Desc => |a: &T, b: &T| a > b,
+    };
+    sort_by_predicate_with_buffer(data, predicate, merge, buffer);
+}
+
+pub fn sort_by_predicate_with_buffer<T, P>(
+    data: &mut [T],
+    predicate: P,
+    merge: Merge,
+    buffer: &mut Vec<T>,
+) where
+    T: Clone,
+    P: Fn(&T, &T) -> bool,
+{
+    match merge {
+        Merge::TopDown => sort_top_down(data, &predicate, buffer),
+        Merge::BottomUp => sort_bottom_up(data, &predicate, buffer),
+    }
+}
+
+fn sort_top_down<T, P>(data: &mut [T], predicate: &P, buffer: &mut Vec<T>)
+where
+    T: Clone,
+    P: Fn(&T, &T) -> bool,
+{
+    if data.len() < 2 {
+        return;
+    }
+
+    let mid = data.len() / 2;
+    sort_top_down(&mut data[..mid], predicate, buffer);
+    sort_top_down(&mut data[mid..], predicate, buffer);
+    merge_by_predicate(data, mid, predicate, buffer);
+}
+
+fn sort_bottom_up<T, P>(data: &mut [T], predicate: &P, buffer: &mut Vec<T>)
+where
+    T: Clone,
+    P: Fn(&T, &T) -> bool,
+{
+    let len = data.len();
+    let mut width = 1;
+
+    while width < len {
+        for start in (0..len).step_by(2 * width) {
+            let mid = (start + width).min(len);
+            let end = (start + 2 * width).min(len);
+            merge_by_predicate(&mut data[start..end], mid - start, predicate, buffer);
+        }
+        width *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{random_vec, Car, Person};
+
+    #[test]
+    fn sort_array_asc() {
+        let mut arr = [2, 3, 1, 5, 4];
+        sort(&mut arr, Order::Asc, Merge::TopDown);
+        assert_eq!([1, 2, 3, 4, 5], arr);
+
+        let mut arr = [2, 3, 1, 5, 4];
+        sort(&mut arr, Order::Asc, Merge::BottomUp);
+        assert_eq!([1, 2, 3, 4, 5], arr);
+
+        let mut arr = [
+            5, 3, 8, 2, 1, 5, 7, 2, 6, 3, 0, 2, 4, 6, 8, 9, 9, 1, 5, 4, 3, 2, 4,
+        ];
+        sort(&mut arr, Order::Asc, Merge::TopDown);
+        assert_eq!(
+            [0, 1, 1, 2, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 7, 8, 8, 9, 9],
+            arr
+        );
+    }
+
+    #[test]
+    fn sort_array_desc() {
+        let mut arr = [2, 3, 1, 5, 4];
+        sort(&mut arr, Order::Desc, Merge::TopDown);
+        assert_eq!([5, 4, 3, 2, 1], arr);
+
+        let mut arr = [2, 3, 1, 5, 4];
+        sort(&mut arr, Order::Desc, Merge::BottomUp);
+        assert_eq!([5, 4, 3, 2, 1], arr);
+    }
+
+    #[test]
+    fn sort_random_vector() {
+        let mut expected = random_vec(1000, 5);
+        expected.sort();
+
+        for merge in [Merge::TopDown, Merge::BottomUp] {
+            let mut vec = random_vec(1000, 5);
+            sort(&mut vec, Order::Asc, merge);
+            assert_eq!(expected, vec);
+        }
+    }
+
+    #[test]
+    fn sort_is_stable() {
+        let keys = random_vec(500, 9);
+        let mut expected: Vec<(u32, usize)> = keys.iter().map(|k| k % 10).zip(0..).collect();
+        expected.sort_by_key(|(k, _)| *k);
+
+        for merge in [Merge::TopDown, Merge::BottomUp] {
+            let mut vec: Vec<(u32, usize)> = keys.iter().map(|k| k % 10).zip(0..).collect();
+            sort_by_predicate(&mut vec, |a, b| a.0 < b.0, merge);
+            assert_eq!(expected, vec);
+        }
+    }
+
+    #[test]
+    fn sort_reusing_buffer() {
+        let mut buffer = Vec::new();
+
+        let mut arr = [2, 3, 1, 5, 4];
+        sort_with_buffer(&mut arr, Order::Asc, Merge::TopDown, &mut buffer);
+        assert_eq!([1, 2, 3, 4, 5], arr);
+
+        let mut arr = [2, 3, 1, 5, 4];
+        sort_with_buffer(&mut arr, Order::Desc, Merge::BottomUp, &mut buffer);
+        assert_eq!([5, 4, 3, 2, 1], arr);
+    }
+
+    #[test]
+    fn sort_struct_vector() {
+        let person1 = Person::new(25);
+        let person2 = Person::new(15);
+        let person3 = Person::new(35);
+
+        let mut vec = vec![person1, person2, person3];
+        sort(&mut vec, Order::Asc, Merge::TopDown);
+        assert_eq!(vec![person2, person1, person3], vec);
+    }
+
+    #[test]
+    fn sort_array_by_predicate() {
+        let mut arr = [
+            5, 3, 8, 2, 1, 5, 7, 2, 6, 3, 0, 2, 4, 6, 8, 9, 9, 1, 5, 4, 3, 2, 4,
+        ];
+        sort_by_predicate(&mut arr, |a, b| a < b, Merge::BottomUp);
+        assert_eq!(
+            [0, 1, 1, 2, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 7, 8, 8, 9, 9],
+            arr
+        );
+    }
+
+    #[test]
+    fn sort_struct_vector_by_predicate() {
+        let car1 = Car { age: 25 };
+        let car2 = Car { age: 15 };
+        let car3 = Car { age: 35 };
+
+        let mut vec = vec![car1, car2, car3];
+        sort_by_predicate(&mut vec, |c1, c2| c1.age < c2.age, Merge::TopDown);
+        assert_eq!(vec![car2, car1, car3], vec);
+    }
+}
@@ -0,0 +1,4 @@
+pub(crate) mod merge;
+mod merge_sort;
+
+pub use merge_sort::*;
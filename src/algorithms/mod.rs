@@ -1,5 +1,15 @@
+#![allow(clippy::module_inception)]
+
+pub mod block_sort;
 pub mod bubble_sort;
 pub mod heap_sort;
 pub mod insertion_sort;
+pub mod merge_sort;
+pub mod partial_sort;
+pub mod pdq_sort;
+pub mod power_sort;
 pub mod quick_sort;
+pub mod select;
 pub mod selection_sort;
+pub mod smooth_sort;
+pub mod tim_sort;
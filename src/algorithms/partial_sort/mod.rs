@@ -0,0 +1,3 @@
+mod partial_sort;
+
+pub use partial_sort::*;
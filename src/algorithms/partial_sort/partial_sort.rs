@@ -0,0 +1,136 @@
+use crate::algorithms::heap_sort::heap::{create_heap_by_predicate, down_heap_by_predicate};
+use crate::algorithms::{heap_sort, pdq_sort, select};
+use crate::Order;
+
+// Below len / HEAP_RATIO the bounded heap rejects most elements with a single
+// comparison against its root, above it selecting first is cheaper.
+const HEAP_RATIO: usize = 8;
+
+// Leaves the first k elements of the sorted slice sorted at its front and the
+// rest in unspecified order. A `k` past the end sorts the whole slice.
+pub fn partial_sort<T: PartialOrd>(data: &mut [T], k: usize, order: Order) {
+    let predicate = match order {
+        Order::Asc => |a: &T, b: &T| a < b,
+        Order::Desc => |a: &T, b: &T| a > b,
+    };
+    top_k_by_predicate(data, k, predicate);
+}
+
+pub fn top_k_by_predicate<T, P>(data: &mut [T], k: usize, predicate: P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let k = k.min(data.len());
+    if k == 0 {
+        return;
+    }
+
+    if k < data.len() / HEAP_RATIO {
+        top_k_with_heap(data, k, &predicate);
+    } else {
+        top_k_with_select(data, k, &predicate);
+    }
+}
+
+fn top_k_with_heap<T, P>(data: &mut [T], k: usize, predicate: &P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    // The root of the heap is the greatest of the k least elements seen so
+    // far, anything less than it replaces it.
+    create_heap_by_predicate(&mut data[..k], predicate);
+    for j in k..data.len() {
+        if predicate(&data[j], &data[0]) {
+            data.swap(0, j);
+            down_heap_by_predicate(data, 0, k, predicate);
+        }
+    }
+
+    heap_sort::sort_heap_by_predicate(&mut data[..k], predicate);
+}
+
+fn top_k_with_select<T, P>(data: &mut [T], k: usize, predicate: &P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    // Deterministic selection groups keys equal to the pivot, so equal
+    // scores, the common case here, cannot make it quadratic.
+    select::select_nth_deterministic_by_predicate(data, k - 1, predicate);
+    pdq_sort::sort_by_predicate(&mut data[..k - 1], predicate);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::helpers::{random_vec, Car};
+
+    #[test]
+    fn partial_sort_array() {
+        let mut arr = [3, 2, 4, 2, 5, 7, 1, 0, 6];
+        partial_sort(&mut arr, 3, Order::Asc);
+        assert_eq!(arr[..3], [0, 1, 2]);
+
+        let mut arr = [3, 2, 4, 2, 5, 7, 1, 0, 6];
+        partial_sort(&mut arr, 4, Order::Desc);
+        assert_eq!(arr[..4], [7, 6, 5, 4]);
+
+        let mut arr = [3, 2, 4];
+        partial_sort(&mut arr, 10, Order::Asc);
+        assert_eq!(arr, [2, 3, 4]);
+
+        let mut arr = [3, 2, 4];
+        partial_sort(&mut arr, 0, Order::Asc);
+        assert_eq!(arr, [3, 2, 4]);
+    }
+
+    #[test]
+    fn partial_sort_random_vector() {
+        let mut expected = random_vec(10_000, 107);
+        expected.sort();
+
+        // Small k goes through the heap, large k through selection.
+        for k in [1, 20, 1000, 5000, 10_000] {
+            let mut vec = random_vec(10_000, 107);
+            partial_sort(&mut vec, k, Order::Asc);
+            assert_eq!(vec[..k], expected[..k]);
+
+            vec.sort();
+            assert_eq!(vec, expected);
+        }
+    }
+
+    #[test]
+    fn partial_sort_duplicates_is_n_log_k() {
+        let len = 100_000;
+        for distinct in [1, 3] {
+            let input: Vec<u32> = (0..len).map(|x| x % distinct).collect();
+            let mut expected = input.clone();
+            expected.sort();
+
+            let k = len as usize / 2;
+            let comparisons = Cell::new(0);
+            let mut vec = input;
+            top_k_by_predicate(&mut vec, k, |a, b| {
+                comparisons.set(comparisons.get() + 1);
+                a < b
+            });
+
+            assert_eq!(vec[..k], expected[..k]);
+            assert!(comparisons.get() < 20 * len as usize);
+        }
+    }
+
+    #[test]
+    fn top_k_struct_vector() {
+        let car1 = Car { age: 25 };
+        let car2 = Car { age: 15 };
+        let car3 = Car { age: 35 };
+        let car4 = Car { age: 5 };
+
+        let mut vec = vec![car1, car2, car3, car4];
+        top_k_by_predicate(&mut vec, 2, |c1, c2| c1.age > c2.age);
+        assert_eq!(vec[..2], [car3, car1]);
+    }
+}
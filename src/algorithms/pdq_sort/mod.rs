@@ -0,0 +1,3 @@
+mod pdq_sort;
+
+pub use pdq_sort::*;
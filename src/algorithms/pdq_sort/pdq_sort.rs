@@ -0,0 +1,316 @@
+use std::mem;
+
+use crate::algorithms::quick_sort::partition::block_partition_by_predicate;
+use crate::algorithms::{heap_sort, insertion_sort};
+use crate::{Heap, Order};
+
+const INSERTION_THRESHOLD: usize = 20;
+const NINTHER_THRESHOLD: usize = 50;
+const MAX_PIVOT_SWAPS: usize = 4 * 3;
+const PARTIAL_INSERTION_STEPS: usize = 5;
+const PARTIAL_INSERTION_SHIFT_THRESHOLD: usize = 50;
+
+pub fn sort<T: PartialOrd>(data: &mut [T], order: Order) {
+    let predicate = match order {
+        Order::Asc => |a: &T, b: &T| a < b,
+        Order::Desc => |a: &T, b: &T| a > b,
+    };
+    sort_by_predicate(data, predicate);
+}
+
+pub fn sort_by_predicate<T, P>(data: &mut [T], predicate: P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    if data.len() < 2 {
+        return;
+    }
+
+    let limit = (usize::BITS - data.len().leading_zeros()) as usize;
+    sort_by_predicate_rec(data, &predicate, None, limit);
+}
+
+fn sort_by_predicate_rec<'a, T, P>(
+    mut data: &'a mut [T],
+    predicate: &P,
+    mut ancestor: Option<&'a T>,
+    mut limit: usize,
+) where
+    P: Fn(&T, &T) -> bool,
+{
+    let mut was_balanced = true;
+    let mut was_partitioned = true;
+
+    loop {
+        let len = data.len();
+
+        if len <= INSERTION_THRESHOLD {
+            insertion_sort::sort_by_predicate(data, predicate);
+            return;
+        }
+        if limit == 0 {
+            heap_sort::sort_by_predicate(data, predicate, Heap::Standard);
+            return;
+        }
+
+        if !was_balanced {
+            break_patterns(data);
+            limit -= 1;
+        }
+
+        let (pivot, likely_sorted) = choose_pivot(data, predicate);
+
+        // The last partition was balanced and did not swap anything, so the
+        // data is probably already sorted: try to finish it off cheaply.
+        if was_balanced
+            && was_partitioned
+            && likely_sorted
+            && partial_insertion_sort(data, predicate)
+        {
+            return;
+        }
+
+        data.swap(0, pivot);
+
+        // The element before this range is not smaller than the pivot, so
+        // nothing here is smaller than the pivot either. Put every element
+        // equal to it on the left and continue with the rest only.
+        if let Some(ancestor) = ancestor {
+            if !predicate(ancestor, &data[0]) {
+                let less_or_equal = |a: &T, b: &T| !predicate(b, a);
+                let (mid, _) = block_partition_by_predicate(data, &less_or_equal);
+                data = &mut mem::take(&mut data)[mid + 1..];
+                continue;
+            }
+        }
+
+        let (mid, partitioned) = block_partition_by_predicate(data, predicate);
+        was_balanced = mid.min(len - mid) >= len / 8;
+        was_partitioned = partitioned;
+
+        let (left, right) = mem::take(&mut data).split_at_mut(mid);
+        let (pivot, right) = right.split_at_mut(1);
+        let pivot = &pivot[0];
+
+        if left.len() < right.len() {
+            sort_by_predicate_rec(left, predicate, ancestor, limit);
+            data = right;
+            ancestor = Some(pivot);
+        } else {
+            sort_by_predicate_rec(right, predicate, Some(pivot), limit);
+            data = left;
+        }
+    }
+}
+
+fn choose_pivot<T, P>(data: &mut [T], predicate: &P) -> (usize, bool)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let len = data.len();
+    let mut a = len / 4;
+    let mut b = len / 4 * 2;
+    let mut c = len / 4 * 3;
+    let mut swaps = 0;
+
+    if len >= NINTHER_THRESHOLD {
+        for i in [&mut a, &mut b, &mut c] {
+            let (mut lo, mut hi) = (*i - 1, *i + 1);
+            sort3(data, predicate, &mut lo, i, &mut hi, &mut swaps);
+        }
+    }
+    sort3(data, predicate, &mut a, &mut b, &mut c, &mut swaps);
+
+    if swaps < MAX_PIVOT_SWAPS {
+        (b, swaps == 0)
+    } else {
+        // Every candidate was out of order, so the data is most likely
+        // descending. Reversing it makes the following partitions cheap.
+        data.reverse();
+        (len - 1 - b, true)
+    }
+}
+
+fn sort2<T, P>(data: &[T], predicate: &P, a: &mut usize, b: &mut usize, swaps: &mut usize)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    if predicate(&data[*b], &data[*a]) {
+        mem::swap(a, b);
+        *swaps += 1;
+    }
+}
+
+fn sort3<T, P>(
+    data: &[T],
+    predicate: &P,
+    a: &mut usize,
+    b: &mut usize,
+    c: &mut usize,
+    swaps: &mut usize,
+) where
+    P: Fn(&T, &T) -> bool,
+{
+    sort2(data, predicate, a, b, swaps);
+    sort2(data, predicate, b, c, swaps);
+    sort2(data, predicate, a, b, swaps);
+}
+
+fn partial_insertion_sort<T, P>(data: &mut [T], predicate: &P) -> bool
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let len = data.len();
+    let mut i = 1;
+
+    for _ in 0..PARTIAL_INSERTION_STEPS {
+        while i < len && !predicate(&data[i], &data[i - 1]) {
+            i += 1;
+        }
+        if i == len {
+            return true;
+        }
+        if len < PARTIAL_INSERTION_SHIFT_THRESHOLD {
+            return false;
+        }
+
+        data.swap(i - 1, i);
+        shift_tail(&mut data[..i], predicate);
+        shift_head(&mut data[i..], predicate);
+    }
+
+    false
+}
+
+fn shift_tail<T, P>(data: &mut [T], predicate: &P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let mut j = data.len() - 1;
+    while j > 0 && predicate(&data[j], &data[j - 1]) {
+        data.swap(j - 1, j);
+        j -= 1;
+    }
+}
+
+fn shift_head<T, P>(data: &mut [T], predicate: &P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let mut j = 0;
+    while j + 1 < data.len() && predicate(&data[j + 1], &data[j]) {
+        data.swap(j, j + 1);
+        j += 1;
+    }
+}
+
+fn break_patterns<T>(data: &mut [T]) {
+    let len = data.len();
+    let mut random = len as u32;
+    let modulus = len.next_power_of_two();
+    let pos = len / 4 * 2;
+
+    for i in 0..3 {
+        random ^= random << 13;
+        random ^= random >> 17;
+        random ^= random << 5;
+
+        let mut other = random as usize & (modulus - 1);
+        if other >= len {
+            other -= len;
+        }
+        data.swap(pos - 1 + i, other);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{random_vec, Car, Person};
+
+    #[test]
+    fn sort_array_asc() {
+        let mut arr = [2, 3, 1, 5, 4];
+        sort(&mut arr, Order::Asc);
+        assert_eq!([1, 2, 3, 4, 5], arr);
+
+        let mut arr = [
+            5, 3, 8, 2, 1, 5, 7, 2, 6, 3, 0, 2, 4, 6, 8, 9, 9, 1, 5, 4, 3, 2, 4,
+        ];
+        sort(&mut arr, Order::Asc);
+        assert_eq!(
+            [0, 1, 1, 2, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 7, 8, 8, 9, 9],
+            arr
+        );
+    }
+
+    #[test]
+    fn sort_array_desc() {
+        let mut arr = [2, 3, 1, 5, 4];
+        sort(&mut arr, Order::Desc);
+        assert_eq!([5, 4, 3, 2, 1], arr);
+    }
+
+    #[test]
+    fn sort_patterns() {
+        let len = 5000;
+        let patterns: Vec<Vec<u32>> = vec![
+            (0..len).collect(),
+            (0..len).rev().collect(),
+            (0..len).map(|i| i % 4).collect(),
+            (0..len).map(|i| i % 100).collect(),
+            (0..len)
+                .map(|i| if i < len / 2 { i } else { len - i })
+                .collect(),
+            random_vec(len as usize, 11),
+        ];
+
+        for pattern in patterns {
+            let mut expected = pattern.clone();
+            expected.sort();
+
+            let mut vec = pattern.clone();
+            sort(&mut vec, Order::Asc);
+            assert_eq!(expected, vec);
+
+            expected.reverse();
+            let mut vec = pattern;
+            sort(&mut vec, Order::Desc);
+            assert_eq!(expected, vec);
+        }
+    }
+
+    #[test]
+    fn sort_struct_vector() {
+        let person1 = Person::new(25);
+        let person2 = Person::new(15);
+        let person3 = Person::new(35);
+
+        let mut vec = vec![person1, person2, person3];
+        sort(&mut vec, Order::Asc);
+        assert_eq!(vec![person2, person1, person3], vec);
+    }
+
+    #[test]
+    fn sort_array_by_predicate() {
+        let mut arr = [
+            5, 3, 8, 2, 1, 5, 7, 2, 6, 3, 0, 2, 4, 6, 8, 9, 9, 1, 5, 4, 3, 2, 4,
+        ];
+        sort_by_predicate(&mut arr, |a, b| a < b);
+        assert_eq!(
+            [0, 1, 1, 2, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 7, 8, 8, 9, 9],
+            arr
+        );
+    }
+
+    #[test]
+    fn sort_struct_vector_by_predicate() {
+        let car1 = Car { age: 25 };
+        let car2 = Car { age: 15 };
+        let car3 = Car { age: 35 };
+
+        let mut vec = vec![car1, car2, car3];
+        sort_by_predicate(&mut vec, |c1, c2| c1.age < c2.age);
+        assert_eq!(vec![car2, car1, car3], vec);
+    }
+}
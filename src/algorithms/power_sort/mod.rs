@@ -0,0 +1,3 @@
+mod power_sort;
+
+pub use power_sort::*;
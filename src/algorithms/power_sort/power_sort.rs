@@ -0,0 +1,219 @@
+use crate::algorithms::tim_sort::merge::{merge_by_predicate, MIN_GALLOP};
+use crate::algorithms::tim_sort::run::{
+    binary_insertion_sort_by_predicate, find_run_by_predicate, min_run_length,
+};
+use crate::Order;
+
+struct Run {
+    start: usize,
+    len: usize,
+    power: u32,
+}
+
+pub fn sort<T: PartialOrd + Clone>(data: &mut [T], order: Order) {
+    let predicate = match order {
+        Order::Asc => |a: &T, b: &T| a < b,
+        Order::Desc => |a: &T, b: &T| a > b,
+    };
+    sort_by_predicate(data, predicate);
+}
+
+pub fn sort_by_predicate<T, P>(data: &mut [T], predicate: P)
+where
+    T: Clone,
+    P: Fn(&T, &T) -> bool,
+{
+    let len = data.len();
+    if len < 2 {
+        return;
+    }
+
+    let min_run = min_run_length(len);
+    let mut runs: Vec<Run> = Vec::new();
+    let mut buffer = Vec::new();
+    let mut min_gallop = MIN_GALLOP;
+
+    let mut current = next_run(data, 0, min_run, &predicate);
+    while current.start + current.len < len {
+        let next = next_run(data, current.start + current.len, min_run, &predicate);
+        let power = node_power(current.start, current.len, next.len, len);
+
+        // Runs on the stack whose boundary is deeper in the merge tree than
+        // the new one have to be merged before the new boundary is pushed.
+        while runs.last().is_some_and(|top| top.power > power) {
+            let top = runs.pop().unwrap();
+            current = merge(data, top, current, &predicate, &mut buffer, &mut min_gallop);
+        }
+
+        runs.push(Run { power, ..current });
+        current = next;
+    }
+
+    while let Some(top) = runs.pop() {
+        current = merge(data, top, current, &predicate, &mut buffer, &mut min_gallop);
+    }
+}
+
+fn next_run<T, P>(data: &mut [T], start: usize, min_run: usize, predicate: &P) -> Run
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let mut len = find_run_by_predicate(&mut data[start..], predicate);
+    if len < min_run {
+        let forced_len = min_run.min(data.len() - start);
+        binary_insertion_sort_by_predicate(&mut data[start..start + forced_len], len, predicate);
+        len = forced_len;
+    }
+
+    Run {
+        start,
+        len,
+        power: 0,
+    }
+}
+
+// The power of the boundary between two adjacent runs is the depth at which
+// their midpoints, scaled to [0, 1), first fall into different halves of a
+// perfectly balanced merge tree over the whole input.
+fn node_power(start: usize, len1: usize, len2: usize, n: usize) -> u32 {
+    let mut a = 2 * start + len1;
+    let mut b = a + len1 + len2;
+    let mut power = 0;
+
+    loop {
+        power += 1;
+        if a >= n {
+            a -= n;
+            b -= n;
+        } else if b >= n {
+            break;
+        }
+        a <<= 1;
+        b <<= 1;
+    }
+    power
+}
+
+fn merge<T, P>(
+    data: &mut [T],
+    left: Run,
+    right: Run,
+    predicate: &P,
+    buffer: &mut Vec<T>,
+    min_gallop: &mut usize,
+) -> Run
+where
+    T: Clone,
+    P: Fn(&T, &T) -> bool,
+{
+    let end = right.start + right.len;
+    merge_by_predicate(
+        &mut data[left.start..end],
+        left.len,
+        predicate,
+        buffer,
+        min_gallop,
+    );
+
+    Run {
+        start: left.start,
+        len: end - left.start,
+        power: right.power,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{random_vec, Car, Person};
+
+    #[test]
+    fn sort_array_asc() {
+        let mut arr = [2, 3, 1, 5, 4];
+        sort(&mut arr, Order::Asc);
+        assert_eq!([1, 2, 3, 4, 5], arr);
+
+        let mut arr = [
+            5, 3, 8, 2, 1, 5, 7, 2, 6, 3, 0, 2, 4, 6, 8, 9, 9, 1, 5, 4, 3, 2, 4,
+        ];
+        sort(&mut arr, Order::Asc);
+        assert_eq!(
+            [0, 1, 1, 2, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 7, 8, 8, 9, 9],
+            arr
+        );
+    }
+
+    #[test]
+    fn sort_array_desc() {
+        let mut arr = [2, 3, 1, 5, 4];
+        sort(&mut arr, Order::Desc);
+        assert_eq!([5, 4, 3, 2, 1], arr);
+    }
+
+    #[test]
+    fn node_power_test() {
+        assert_eq!(node_power(0, 4, 4, 8), 1);
+        assert_eq!(node_power(0, 2, 2, 8), 2);
+        assert_eq!(node_power(4, 2, 2, 8), 2);
+        assert_eq!(node_power(2, 1, 1, 8), 3);
+    }
+
+    #[test]
+    fn sort_natural_runs() {
+        let mut vec: Vec<u32> = (0..3000).collect();
+        vec.extend((0..2000).rev());
+        vec.extend(random_vec(1000, 19));
+        vec.extend(0..500);
+
+        let mut expected = vec.clone();
+        expected.sort();
+
+        sort(&mut vec, Order::Asc);
+        assert_eq!(expected, vec);
+    }
+
+    #[test]
+    fn sort_is_stable() {
+        let keys = random_vec(5000, 23);
+        let mut expected: Vec<(u32, usize)> = keys.iter().map(|k| k % 10).zip(0..).collect();
+        expected.sort_by_key(|(k, _)| *k);
+
+        let mut vec: Vec<(u32, usize)> = keys.iter().map(|k| k % 10).zip(0..).collect();
+        sort_by_predicate(&mut vec, |a, b| a.0 < b.0);
+        assert_eq!(expected, vec);
+    }
+
+    #[test]
+    fn sort_struct_vector() {
+        let person1 = Person::new(25);
+        let person2 = Person::new(15);
+        let person3 = Person::new(35);
+
+        let mut vec = vec![person1, person2, person3];
+        sort(&mut vec, Order::Asc);
+        assert_eq!(vec![person2, person1, person3], vec);
+    }
+
+    #[test]
+    fn sort_array_by_predicate() {
+        let mut arr = [
+            5, 3, 8, 2, 1, 5, 7, 2, 6, 3, 0, 2, 4, 6, 8, 9, 9, 1, 5, 4, 3, 2, 4,
+        ];
+        sort_by_predicate(&mut arr, |a, b| a < b);
+        assert_eq!(
+            [0, 1, 1, 2, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 7, 8, 8, 9, 9],
+            arr
+        );
+    }
+
+    #[test]
+    fn sort_struct_vector_by_predicate() {
+        let car1 = Car { age: 25 };
+        let car2 = Car { age: 15 };
+        let car3 = Car { age: 35 };
+
+        let mut vec = vec![car1, car2, car3];
+        sort_by_predicate(&mut vec, |c1, c2| c1.age < c2.age);
+        assert_eq!(vec![car2, car1, car3], vec);
+    }
+}
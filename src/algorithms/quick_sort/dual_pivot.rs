@@ -0,0 +1,230 @@
+use super::pivot::PivotStrategy;
+use crate::algorithms::{heap_sort, insertion_sort};
+use crate::{Heap, Order};
+
+const INSERTION_THRESHOLD: usize = 16;
+
+pub fn dual_pivot_sort<T: PartialOrd, S: PivotStrategy>(
+    data: &mut [T],
+    order: Order,
+    partition: S,
+) {
+    let predicate = match order {
+        Order::Asc => |a: &T, b: &T| a < b,
+        Order::Desc => |a: &T, b: &T| a > b,
+    };
+    dual_pivot_sort_by_predicate(data, predicate, partition);
+}
+
+pub fn dual_pivot_sort_by_predicate<T, P, S>(data: &mut [T], predicate: P, partition: S)
+where
+    P: Fn(&T, &T) -> bool,
+    S: PivotStrategy,
+{
+    if data.len() < 2 {
+        return;
+    }
+
+    let depth_limit = 2 * data.len().ilog2() as usize;
+    sort_by_predicate_rec(data, 0, data.len() - 1, depth_limit, &predicate, &partition)
+}
+
+fn sort_by_predicate_rec<T, P, S>(
+    data: &mut [T],
+    mut l: usize,
+    mut r: usize,
+    mut depth_limit: usize,
+    predicate: &P,
+    partition: &S,
+) where
+    P: Fn(&T, &T) -> bool,
+    S: PivotStrategy,
+{
+    // As in `sort_by_predicate`, only the smaller parts are sorted
+    // recursively and the largest one by the next iteration.
+    loop {
+        if r - l < INSERTION_THRESHOLD {
+            insertion_sort::sort_by_predicate(&mut data[l..=r], predicate);
+            return;
+        }
+        if depth_limit == 0 {
+            heap_sort::sort_by_predicate(&mut data[l..=r], predicate, Heap::Standard);
+            return;
+        }
+        depth_limit -= 1;
+
+        let (lt, gt) = dual_pivot_partition_by_predicate(data, l, r, predicate, partition);
+
+        // Parts as `[start, end)`. With equal pivots everything between them
+        // is equal to both, so the middle part is already sorted.
+        let mut parts = [(l, lt), (lt + 1, gt), (gt + 1, r + 1)];
+        if !predicate(&data[lt], &data[gt]) {
+            parts[1] = (gt, gt);
+        }
+
+        let largest = (0..parts.len())
+            .max_by_key(|&i| parts[i].1 - parts[i].0)
+            .unwrap();
+        for (i, &(start, end)) in parts.iter().enumerate() {
+            if i != largest && end - start > 1 {
+                sort_by_predicate_rec(data, start, end - 1, depth_limit, predicate, partition);
+            }
+        }
+
+        let (start, end) = parts[largest];
+        if end - start < 2 {
+            return;
+        }
+        (l, r) = (start, end - 1);
+    }
+}
+
+// Yaroslavskiy's partitioning. One pivot is chosen from each half of the
+// range and the range is split into elements less than the lesser pivot,
+// elements between the two and elements greater than the greater pivot. The
+// returned indices are where the two pivots end up.
+fn dual_pivot_partition_by_predicate<T, P, S>(
+    data: &mut [T],
+    l: usize,
+    r: usize,
+    predicate: &P,
+    partition: &S,
+) -> (usize, usize)
+where
+    P: Fn(&T, &T) -> bool,
+    S: PivotStrategy,
+{
+    let mid = l + (r - l) / 2;
+    let first = partition.select_pivot(data, l, mid, predicate);
+    data.swap(l, first);
+    let second = partition.select_pivot(data, mid + 1, r, predicate);
+    data.swap(r, second);
+    if predicate(&data[r], &data[l]) {
+        data.swap(l, r);
+    }
+
+    let (mut lt, mut k, mut gt) = (l + 1, l + 1, r - 1);
+    while k <= gt {
+        if predicate(&data[k], &data[l]) {
+            data.swap(k, lt);
+            lt += 1;
+        } else if !predicate(&data[k], &data[r]) {
+            while k < gt && predicate(&data[r], &data[gt]) {
+                gt -= 1;
+            }
+            data.swap(k, gt);
+            gt -= 1;
+            if predicate(&data[k], &data[l]) {
+                data.swap(k, lt);
+                lt += 1;
+            }
+        }
+        k += 1;
+    }
+
+    lt -= 1;
+    gt += 1;
+    data.swap(l, lt);
+    data.swap(r, gt);
+    (lt, gt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{random_vec, Car, Person};
+    use crate::Partition;
+
+    #[test]
+    fn sort_array_asc() {
+        let mut arr = [2, 3, 1, 5, 4];
+        dual_pivot_sort(&mut arr, Order::Asc, Partition::First);
+        assert_eq!([1, 2, 3, 4, 5], arr);
+
+        let mut arr = [
+            5, 3, 8, 2, 1, 5, 7, 2, 6, 3, 0, 2, 4, 6, 8, 9, 9, 1, 5, 4, 3, 2, 4,
+        ];
+        dual_pivot_sort(&mut arr, Order::Asc, Partition::Center);
+        assert_eq!(
+            [0, 1, 1, 2, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 7, 8, 8, 9, 9],
+            arr
+        );
+    }
+
+    #[test]
+    fn sort_array_desc() {
+        let mut arr = [2, 3, 1, 5, 4];
+        dual_pivot_sort(&mut arr, Order::Desc, Partition::Last);
+        assert_eq!([5, 4, 3, 2, 1], arr);
+    }
+
+    #[test]
+    fn dual_pivot_partition() {
+        let mut vec = random_vec(1000, 157);
+        let (lt, gt) = dual_pivot_partition_by_predicate(
+            &mut vec,
+            0,
+            999,
+            &|a, b| a < b,
+            &Partition::MedianOfThree,
+        );
+
+        let (p, q) = (vec[lt], vec[gt]);
+        assert!(p <= q);
+        assert!(vec[..lt].iter().all(|x| *x < p));
+        assert!(vec[lt + 1..gt].iter().all(|x| p <= *x && *x <= q));
+        assert!(vec[gt + 1..].iter().all(|x| *x >= q));
+    }
+
+    #[test]
+    fn sort_random_vector() {
+        let mut expected = random_vec(5000, 163);
+        expected.sort();
+
+        for partition in [
+            Partition::First,
+            Partition::Center,
+            Partition::Last,
+            Partition::Ninther,
+            Partition::Random(5),
+        ] {
+            let mut vec = random_vec(5000, 163);
+            dual_pivot_sort(&mut vec, Order::Asc, partition);
+            assert_eq!(expected, vec);
+        }
+    }
+
+    #[test]
+    fn sort_sorted_and_duplicates() {
+        let sorted: Vec<u32> = (0..10_000).collect();
+        let mut vec: Vec<u32> = (0..10_000).rev().collect();
+        dual_pivot_sort(&mut vec, Order::Asc, Partition::First);
+        assert_eq!(sorted, vec);
+
+        let mut vec = vec![3; 10_000];
+        dual_pivot_sort(&mut vec, Order::Asc, Partition::Center);
+        assert_eq!(vec![3; 10_000], vec);
+    }
+
+    #[test]
+    fn sort_struct_vector() {
+        let person1 = Person::new(25);
+        let person2 = Person::new(15);
+        let person3 = Person::new(35);
+
+        let mut vec = vec![person1, person2, person3];
+        dual_pivot_sort(&mut vec, Order::Asc, Partition::First);
+        assert_eq!(vec![person2, person1, person3], vec);
+    }
+
+    #[test]
+    fn sort_struct_vector_by_predicate() {
+        let car1 = Car { age: 25 };
+        let car2 = Car { age: 15 };
+        let car3 = Car { age: 35 };
+
+        let mut vec = vec![car1, car2, car3];
+        dual_pivot_sort_by_predicate(&mut vec, |c1, c2| c1.age < c2.age, Partition::First);
+        assert_eq!(vec![car2, car1, car3], vec);
+    }
+}
@@ -1,4 +1,11 @@
-mod partition;
+mod dual_pivot;
+pub(crate) mod partition;
+mod pivot;
 mod quick_sort;
 
+pub use dual_pivot::*;
+pub use partition::{
+    hoare_partition_by_predicate, partition_by_predicate, three_way_partition_by_predicate,
+};
+pub use pivot::PivotStrategy;
 pub use quick_sort::*;
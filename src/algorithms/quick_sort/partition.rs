@@ -1,20 +1,17 @@
-use crate::Partition;
+use super::pivot::PivotStrategy;
 
-pub fn partition_by_predicate<T, P>(
+pub fn partition_by_predicate<T, P, S>(
     data: &mut [T],
     l: usize,
     r: usize,
     predicate: P,
-    partition: &Partition,
+    partition: &S,
 ) -> usize
 where
     P: Fn(&T, &T) -> bool,
+    S: PivotStrategy,
 {
-    let pivot = match partition {
-        Partition::First => l,
-        Partition::Last => r,
-        Partition::Center => usize::from((l + r) / 2),
-    };
+    let pivot = partition.select_pivot(data, l, r, &predicate);
     let mut i = l;
 
     for j in l..=r {
@@ -33,16 +30,176 @@ where
 
     if i <= pivot {
         data.swap(i, pivot);
-        return i;
+        i
     } else {
         data.swap(i - 1, pivot);
-        return i - 1;
+        i - 1
     }
 }
 
+// Hoare's scheme with the pivot moved to `l` first and swapped into its final
+// place at the end, so like `partition_by_predicate` it returns the index of
+// the pivot. Elements equal to the pivot stop both scans, which keeps the
+// halves balanced on duplicates.
+pub fn hoare_partition_by_predicate<T, P, S>(
+    data: &mut [T],
+    l: usize,
+    r: usize,
+    predicate: P,
+    partition: &S,
+) -> usize
+where
+    P: Fn(&T, &T) -> bool,
+    S: PivotStrategy,
+{
+    let pivot = partition.select_pivot(data, l, r, &predicate);
+    data.swap(l, pivot);
+
+    let (mut i, mut j) = (l + 1, r);
+    loop {
+        while i <= j && predicate(&data[i], &data[l]) {
+            i += 1;
+        }
+        while i <= j && predicate(&data[l], &data[j]) {
+            j -= 1;
+        }
+        if i >= j {
+            break;
+        }
+
+        data.swap(i, j);
+        i += 1;
+        j -= 1;
+    }
+
+    data.swap(l, j);
+    j
+}
+
+// Dutch national flag partitioning. Returns the range `[lt, gt)` holding the
+// pivot and every element equal to it, everything before it is less and
+// everything from `gt` to `r` greater.
+pub fn three_way_partition_by_predicate<T, P, S>(
+    data: &mut [T],
+    l: usize,
+    r: usize,
+    predicate: P,
+    partition: &S,
+) -> (usize, usize)
+where
+    P: Fn(&T, &T) -> bool,
+    S: PivotStrategy,
+{
+    let pivot = partition.select_pivot(data, l, r, &predicate);
+    data.swap(l, pivot);
+
+    // `data[lt]` is always equal to the pivot, so it is compared against.
+    let (mut lt, mut i, mut gt) = (l, l + 1, r + 1);
+    while i < gt {
+        if predicate(&data[i], &data[lt]) {
+            data.swap(lt, i);
+            lt += 1;
+            i += 1;
+        } else if predicate(&data[lt], &data[i]) {
+            gt -= 1;
+            data.swap(i, gt);
+        } else {
+            i += 1;
+        }
+    }
+    (lt, gt)
+}
+
+pub(crate) fn block_partition_by_predicate<T, P>(data: &mut [T], predicate: &P) -> (usize, bool)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let (head, tail) = data.split_at_mut(1);
+    let pivot = &head[0];
+
+    let mut l = 0;
+    let mut r = tail.len();
+    while l < r && predicate(&tail[l], pivot) {
+        l += 1;
+    }
+    while l < r && !predicate(&tail[r - 1], pivot) {
+        r -= 1;
+    }
+    let was_partitioned = l >= r;
+
+    let mid = l + partition_in_blocks(&mut tail[l..r], pivot, predicate);
+    data.swap(0, mid);
+    (mid, was_partitioned)
+}
+
+const BLOCK: usize = 64;
+
+fn partition_in_blocks<T, P>(data: &mut [T], pivot: &T, predicate: &P) -> usize
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let mut l = 0;
+    let mut r = data.len();
+
+    let mut offsets_l = [0u8; BLOCK];
+    let mut offsets_r = [0u8; BLOCK];
+    let (mut start_l, mut end_l) = (0, 0);
+    let (mut start_r, mut end_r) = (0, 0);
+
+    while r - l >= 2 * BLOCK {
+        // The offsets of misplaced elements are recorded unconditionally and
+        // the counter is bumped by the comparison result, so filling a block
+        // has no branch that depends on the data.
+        if start_l == end_l {
+            start_l = 0;
+            end_l = 0;
+            for i in 0..BLOCK {
+                offsets_l[end_l] = i as u8;
+                end_l += !predicate(&data[l + i], pivot) as usize;
+            }
+        }
+        if start_r == end_r {
+            start_r = 0;
+            end_r = 0;
+            for i in 0..BLOCK {
+                offsets_r[end_r] = i as u8;
+                end_r += predicate(&data[r - 1 - i], pivot) as usize;
+            }
+        }
+
+        let count = (end_l - start_l).min(end_r - start_r);
+        for k in 0..count {
+            data.swap(
+                l + offsets_l[start_l + k] as usize,
+                r - 1 - offsets_r[start_r + k] as usize,
+            );
+        }
+        start_l += count;
+        start_r += count;
+
+        if start_l == end_l {
+            l += BLOCK;
+        }
+        if start_r == end_r {
+            r -= BLOCK;
+        }
+    }
+
+    let mut i = l;
+    for j in l..r {
+        if predicate(&data[j], pivot) {
+            data.swap(i, j);
+            i += 1;
+        }
+    }
+    i
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::helpers::random_vec;
+    use crate::Partition;
 
     #[test]
     fn partition_first() {
@@ -218,4 +375,90 @@ mod tests {
         assert_eq!(arr[4], 1);
         assert_eq!(pivot, 4);
     }
+
+    #[test]
+    fn block_partition() {
+        let mut arr = [4, 5, 3, 2, 1];
+        let (pivot, was_partitioned) = block_partition_by_predicate(&mut arr, &|a, b| a < b);
+
+        assert!(arr[0] <= 4);
+        assert!(arr[1] <= 4);
+        assert!(arr[2] <= 4);
+        assert_eq!(arr[3], 4);
+        assert_eq!(arr[4], 5);
+        assert_eq!(pivot, 3);
+        assert!(!was_partitioned);
+
+        let mut arr = [3, 1, 2, 4, 5];
+        let (pivot, was_partitioned) = block_partition_by_predicate(&mut arr, &|a, b| a < b);
+
+        assert_eq!(pivot, 2);
+        assert!(was_partitioned);
+    }
+
+    #[test]
+    fn block_partition_large() {
+        let mut vec = random_vec(1000, 3);
+        let (pivot, _) = block_partition_by_predicate(&mut vec, &|a, b| a < b);
+
+        assert!(vec[..pivot].iter().all(|x| *x < vec[pivot]));
+        assert!(vec[pivot + 1..].iter().all(|x| *x >= vec[pivot]));
+    }
+
+    #[test]
+    fn three_way_partition() {
+        let mut arr = [3, 1, 3, 5, 3, 0, 4, 3];
+        let (lt, gt) =
+            three_way_partition_by_predicate(&mut arr, 0, 7, |a, b| a < b, &Partition::First);
+
+        assert_eq!((lt, gt), (2, 6));
+        assert!(arr[..lt].iter().all(|x| *x < 3));
+        assert!(arr[lt..gt].iter().all(|x| *x == 3));
+        assert!(arr[gt..].iter().all(|x| *x > 3));
+    }
+
+    #[test]
+    fn three_way_partition_subrange() {
+        let mut arr = [9, 2, 2, 1, 2, 0];
+        let (lt, gt) =
+            three_way_partition_by_predicate(&mut arr, 1, 4, |a, b| a > b, &Partition::Center);
+
+        assert_eq!((lt, gt), (1, 4));
+        assert_eq!(arr, [9, 2, 2, 2, 1, 0]);
+    }
+
+    #[test]
+    fn hoare_partition() {
+        for partition in [Partition::First, Partition::Center, Partition::Last] {
+            let mut arr = [4, 1, 3, 2, 5];
+            let pivot = hoare_partition_by_predicate(&mut arr, 0, 4, |a, b| a < b, &partition);
+
+            assert!(arr[..pivot].iter().all(|x| *x <= arr[pivot]));
+            assert!(arr[pivot + 1..].iter().all(|x| *x >= arr[pivot]));
+        }
+
+        let mut arr = [4, 1, 3, 2, 5];
+        let pivot = hoare_partition_by_predicate(&mut arr, 0, 4, |a, b| a < b, &Partition::Last);
+        assert_eq!(pivot, 4);
+        assert_eq!(arr[4], 5);
+    }
+
+    #[test]
+    fn hoare_partition_duplicates() {
+        let mut arr = [9, 2, 2, 2, 2, 2, 2, 0];
+        let pivot = hoare_partition_by_predicate(&mut arr, 1, 6, |a, b| a < b, &Partition::First);
+
+        assert_eq!(pivot, 4);
+        assert_eq!(arr, [9, 2, 2, 2, 2, 2, 2, 0]);
+    }
+
+    #[test]
+    fn hoare_partition_large() {
+        let mut vec = random_vec(1000, 151);
+        let pivot =
+            hoare_partition_by_predicate(&mut vec, 0, 999, |a, b| a > b, &Partition::Center);
+
+        assert!(vec[..pivot].iter().all(|x| *x >= vec[pivot]));
+        assert!(vec[pivot + 1..].iter().all(|x| *x <= vec[pivot]));
+    }
 }
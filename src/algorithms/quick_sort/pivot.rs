@@ -0,0 +1,138 @@
+use crate::algorithms::select;
+use crate::Partition;
+
+// Ranges shorter than this use median of three instead of the ninther.
+const NINTHER_THRESHOLD: usize = 40;
+
+// Chooses the pivot for partitioning `data[l..=r]` and returns its index. It
+// may reorder elements inside the range but must not touch anything outside.
+pub trait PivotStrategy {
+    fn select_pivot<T, P>(&self, data: &mut [T], l: usize, r: usize, predicate: &P) -> usize
+    where
+        P: Fn(&T, &T) -> bool;
+}
+
+impl PivotStrategy for Partition {
+    fn select_pivot<T, P>(&self, data: &mut [T], l: usize, r: usize, predicate: &P) -> usize
+    where
+        P: Fn(&T, &T) -> bool,
+    {
+        let center = l + (r - l) / 2;
+        match self {
+            Partition::First => l,
+            Partition::Last => r,
+            Partition::Center => center,
+            Partition::MedianOfThree => median_of_three(data, l, center, r, predicate),
+            Partition::Ninther if r - l + 1 < NINTHER_THRESHOLD => {
+                median_of_three(data, l, center, r, predicate)
+            }
+            Partition::Ninther => {
+                let step = (r - l + 1) / 8;
+                let a = median_of_three(data, l, l + step, l + 2 * step, predicate);
+                let b = median_of_three(data, center - step, center, center + step, predicate);
+                let c = median_of_three(data, r - 2 * step, r - step, r, predicate);
+                median_of_three(data, a, b, c, predicate)
+            }
+            Partition::Random(seed) => l + (random(*seed, l, r) % (r - l + 1) as u64) as usize,
+            Partition::MedianOfMedians => select::median_of_medians(data, l, r, predicate),
+        }
+    }
+}
+
+fn median_of_three<T, P>(data: &[T], a: usize, b: usize, c: usize, predicate: &P) -> usize
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let (x, y, z) = (&data[a], &data[b], &data[c]);
+    if predicate(x, y) {
+        if predicate(y, z) {
+            b
+        } else if predicate(x, z) {
+            c
+        } else {
+            a
+        }
+    } else if predicate(x, z) {
+        a
+    } else if predicate(y, z) {
+        c
+    } else {
+        b
+    }
+}
+
+// SplitMix64 over the seed and the range, so the same seed always picks the
+// same pivots for the same input without keeping any state between calls.
+fn random(seed: u64, l: usize, r: usize) -> u64 {
+    let mut x = seed ^ (l as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ (r as u64).rotate_left(32);
+    x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::random_vec;
+
+    fn less(a: &u32, b: &u32) -> bool {
+        a < b
+    }
+
+    #[test]
+    fn median_of_three_test() {
+        for (arr, median) in [
+            ([1, 2, 3], 1),
+            ([1, 3, 2], 2),
+            ([2, 1, 3], 0),
+            ([2, 3, 1], 0),
+            ([3, 1, 2], 2),
+            ([3, 2, 1], 1),
+            ([2, 2, 2], 1),
+        ] {
+            assert_eq!(median_of_three(&arr, 0, 1, 2, &less), median);
+        }
+    }
+
+    #[test]
+    fn select_pivot_simple() {
+        let mut arr = [5, 9, 7, 3, 1, 8, 4];
+        assert_eq!(Partition::First.select_pivot(&mut arr, 0, 6, &less), 0);
+        assert_eq!(Partition::Last.select_pivot(&mut arr, 0, 6, &less), 6);
+        assert_eq!(Partition::Center.select_pivot(&mut arr, 2, 6, &less), 4);
+        assert_eq!(
+            Partition::MedianOfThree.select_pivot(&mut arr, 0, 6, &less),
+            6
+        );
+        assert_eq!(Partition::Ninther.select_pivot(&mut arr, 0, 6, &less), 6);
+    }
+
+    #[test]
+    fn select_pivot_is_central() {
+        let len = 10_000;
+        for partition in [Partition::Ninther, Partition::MedianOfMedians] {
+            let mut vec: Vec<u32> = (0..len).collect();
+            let pivot = partition.select_pivot(&mut vec, 0, len as usize - 1, &less);
+            assert!((len / 4..3 * len / 4).contains(&vec[pivot]));
+        }
+    }
+
+    #[test]
+    fn select_pivot_random() {
+        let mut vec = random_vec(1000, 137);
+        for (l, r) in [(0, 999), (10, 10), (500, 520)] {
+            let pivot = Partition::Random(7).select_pivot(&mut vec, l, r, &less);
+            assert!((l..=r).contains(&pivot));
+            assert_eq!(
+                Partition::Random(7).select_pivot(&mut vec, l, r, &less),
+                pivot
+            );
+        }
+
+        let pivots: Vec<usize> = (0..10)
+            .map(|seed| Partition::Random(seed).select_pivot(&mut vec, 0, 999, &less))
+            .collect();
+        assert!(pivots.iter().any(|&pivot| pivot != pivots[0]));
+    }
+}
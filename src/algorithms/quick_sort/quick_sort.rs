@@ -1,62 +1,140 @@
-use super::partition::partition_by_predicate;
-use crate::{Order, Partition};
+use super::partition::{
+    block_partition_by_predicate, hoare_partition_by_predicate, partition_by_predicate,
+    three_way_partition_by_predicate,
+};
+use super::pivot::PivotStrategy;
+use crate::algorithms::{heap_sort, insertion_sort};
+use crate::{Heap, Order, Scheme};
 
-pub fn sort<T: PartialOrd>(data: &mut [T], order: Order, partition: Partition) {
+const INSERTION_THRESHOLD: usize = 16;
+
+pub fn sort<T: PartialOrd, S: PivotStrategy>(
+    data: &mut [T],
+    order: Order,
+    partition: S,
+    scheme: Scheme,
+) {
     let predicate = match order {
         Order::Asc => |a: &T, b: &T| a < b,
         Order::Desc => |a: &T, b: &T| a > b,
     };
-    sort_by_predicate(data, predicate, partition);
+    sort_by_predicate(data, predicate, partition, scheme);
 }
 
-pub fn sort_by_predicate<T, P>(data: &mut [T], predicate: P, partition: Partition)
+pub fn sort_by_predicate<T, P, S>(data: &mut [T], predicate: P, partition: S, scheme: Scheme)
 where
     P: Fn(&T, &T) -> bool,
+    S: PivotStrategy,
 {
-    sort_by_predicate_rec(data, 0, data.len() - 1, &predicate, &partition)
+    if data.len() < 2 {
+        return;
+    }
+
+    // Introsort: once the recursion gets deeper than 2·log₂(n) the pivots are
+    // clearly not splitting the data, so the range is finished with heap sort.
+    let depth_limit = 2 * data.len().ilog2() as usize;
+    sort_by_predicate_rec(
+        data,
+        0,
+        data.len() - 1,
+        depth_limit,
+        &predicate,
+        &partition,
+        &scheme,
+    )
 }
 
-fn sort_by_predicate_rec<T, P>(
+fn sort_by_predicate_rec<T, P, S>(
     data: &mut [T],
-    l: usize,
-    r: usize,
+    mut l: usize,
+    mut r: usize,
+    mut depth_limit: usize,
     predicate: &P,
-    partition: &Partition,
+    partition: &S,
+    scheme: &Scheme,
 ) where
     P: Fn(&T, &T) -> bool,
+    S: PivotStrategy,
 {
-    let pivot = partition_by_predicate(data, l, r, predicate, partition);
-    if pivot > l + 1 {
-        sort_by_predicate_rec(data, l, pivot - 1, predicate, partition);
-    }
-    if pivot + 1 < r {
-        sort_by_predicate_rec(data, pivot + 1, r, predicate, partition);
+    // Only the smaller side is sorted recursively, the larger one is sorted
+    // by the next iteration, so the stack never gets deeper than log₂(n)
+    // whatever the pivots are.
+    loop {
+        if r - l < INSERTION_THRESHOLD {
+            insertion_sort::sort_by_predicate(&mut data[l..=r], predicate);
+            return;
+        }
+        if depth_limit == 0 {
+            heap_sort::sort_by_predicate(&mut data[l..=r], predicate, Heap::Standard);
+            return;
+        }
+        depth_limit -= 1;
+
+        // Elements in `[lt, gt)` are equal to the pivot and already in place.
+        let (lt, gt) = match scheme {
+            Scheme::Lomuto => {
+                let pivot = partition_by_predicate(data, l, r, predicate, partition);
+                (pivot, pivot + 1)
+            }
+            Scheme::Hoare => {
+                let pivot = hoare_partition_by_predicate(data, l, r, predicate, partition);
+                (pivot, pivot + 1)
+            }
+            Scheme::ThreeWay => three_way_partition_by_predicate(data, l, r, predicate, partition),
+            Scheme::Block => {
+                let pivot = partition.select_pivot(data, l, r, predicate);
+                data.swap(l, pivot);
+                let (mid, _) = block_partition_by_predicate(&mut data[l..=r], predicate);
+                (l + mid, l + mid + 1)
+            }
+        };
+
+        if lt - l < r + 1 - gt {
+            if lt > l + 1 {
+                sort_by_predicate_rec(data, l, lt - 1, depth_limit, predicate, partition, scheme);
+            }
+            if gt >= r {
+                return;
+            }
+            l = gt;
+        } else {
+            if gt < r {
+                sort_by_predicate_rec(data, gt, r, depth_limit, predicate, partition, scheme);
+            }
+            if lt <= l + 1 {
+                return;
+            }
+            r = lt - 1;
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::cell::Cell;
+
     use super::*;
-    use crate::helpers::{Car, Person};
+    use crate::helpers::{random_vec, Car, Person};
+    use crate::Partition;
 
     #[test]
     fn sort_array_asc() {
         let mut arr = [2, 3, 1, 5, 4];
-        sort(&mut arr, Order::Asc, Partition::First);
+        sort(&mut arr, Order::Asc, Partition::First, Scheme::Lomuto);
         assert_eq!([1, 2, 3, 4, 5], arr);
 
         let mut arr = [2, 3, 1, 5, 4];
-        sort(&mut arr, Order::Asc, Partition::Last);
+        sort(&mut arr, Order::Asc, Partition::Last, Scheme::Lomuto);
         assert_eq!([1, 2, 3, 4, 5], arr);
 
         let mut arr = [2, 3, 1, 5, 4];
-        sort(&mut arr, Order::Asc, Partition::Center);
+        sort(&mut arr, Order::Asc, Partition::Center, Scheme::Lomuto);
         assert_eq!([1, 2, 3, 4, 5], arr);
 
         let mut arr = [
             5, 3, 8, 2, 1, 5, 7, 2, 6, 3, 0, 2, 4, 6, 8, 9, 9, 1, 5, 4, 3, 2, 4,
         ];
-        sort(&mut arr, Order::Asc, Partition::First);
+        sort(&mut arr, Order::Asc, Partition::First, Scheme::Lomuto);
         assert_eq!(
             [0, 1, 1, 2, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 7, 8, 8, 9, 9],
             arr
@@ -66,18 +144,118 @@ mod tests {
     #[test]
     fn sort_array_desc() {
         let mut arr = [2, 3, 1, 5, 4];
-        sort(&mut arr, Order::Desc, Partition::First);
+        sort(&mut arr, Order::Desc, Partition::First, Scheme::Lomuto);
         assert_eq!([5, 4, 3, 2, 1], arr);
 
         let mut arr = [2, 3, 1, 5, 4];
-        sort(&mut arr, Order::Desc, Partition::Last);
+        sort(&mut arr, Order::Desc, Partition::Last, Scheme::Lomuto);
         assert_eq!([5, 4, 3, 2, 1], arr);
 
         let mut arr = [2, 3, 1, 5, 4];
-        sort(&mut arr, Order::Desc, Partition::Center);
+        sort(&mut arr, Order::Desc, Partition::Center, Scheme::Lomuto);
         assert_eq!([5, 4, 3, 2, 1], arr);
     }
 
+    fn partitions() -> [Partition; 7] {
+        [
+            Partition::First,
+            Partition::Center,
+            Partition::Last,
+            Partition::MedianOfThree,
+            Partition::Ninther,
+            Partition::Random(42),
+            Partition::MedianOfMedians,
+        ]
+    }
+
+    #[test]
+    fn sort_empty_and_single() {
+        let mut arr: [i32; 0] = [];
+        sort(&mut arr, Order::Asc, Partition::First, Scheme::Lomuto);
+        assert_eq!([0; 0], arr);
+
+        let mut arr = [1];
+        sort(&mut arr, Order::Asc, Partition::First, Scheme::Lomuto);
+        assert_eq!([1], arr);
+    }
+
+    fn schemes() -> [Scheme; 4] {
+        [
+            Scheme::Lomuto,
+            Scheme::Hoare,
+            Scheme::ThreeWay,
+            Scheme::Block,
+        ]
+    }
+
+    #[test]
+    fn sort_sorted_and_reversed() {
+        let sorted: Vec<u32> = (0..10_000).collect();
+        let reversed: Vec<u32> = (0..10_000).rev().collect();
+
+        for scheme in schemes() {
+            for partition in partitions() {
+                let mut vec = sorted.clone();
+                sort(&mut vec, Order::Asc, partition, scheme);
+                assert_eq!(sorted, vec);
+            }
+        }
+        for scheme in schemes() {
+            for partition in partitions() {
+                let mut vec = sorted.clone();
+                sort(&mut vec, Order::Desc, partition, scheme);
+                assert_eq!(reversed, vec);
+            }
+        }
+    }
+
+    #[test]
+    fn sort_random_vector() {
+        let mut expected = random_vec(1000, 7);
+        expected.sort();
+
+        for scheme in schemes() {
+            for partition in partitions() {
+                let mut vec = random_vec(1000, 7);
+                sort(&mut vec, Order::Asc, partition, scheme);
+                assert_eq!(expected, vec);
+            }
+        }
+    }
+
+    #[test]
+    fn sort_large_sorted_with_first_pivot() {
+        let sorted: Vec<u32> = (0..100_000).collect();
+
+        for scheme in schemes() {
+            let mut vec = sorted.clone();
+            sort(&mut vec, Order::Asc, Partition::First, scheme);
+            assert_eq!(sorted, vec);
+        }
+    }
+
+    #[test]
+    fn sort_few_distinct_is_linear() {
+        let vec: Vec<u32> = random_vec(100_000, 149).iter().map(|x| x % 4).collect();
+        let mut expected = vec.clone();
+        expected.sort();
+
+        let comparisons = Cell::new(0);
+        let mut vec = vec;
+        sort_by_predicate(
+            &mut vec,
+            |a, b| {
+                comparisons.set(comparisons.get() + 1);
+                a < b
+            },
+            Partition::Center,
+            Scheme::ThreeWay,
+        );
+
+        assert_eq!(expected, vec);
+        assert!(comparisons.get() < 10 * vec.len());
+    }
+
     #[test]
     fn sort_struct_vector() {
         let person1 = Person::new(25);
@@ -85,7 +263,7 @@ mod tests {
         let person3 = Person::new(35);
 
         let mut vec = vec![person1, person2, person3];
-        sort(&mut vec, Order::Asc, Partition::First);
+        sort(&mut vec, Order::Asc, Partition::First, Scheme::Lomuto);
         assert_eq!(vec![person2, person1, person3], vec);
     }
 
@@ -94,7 +272,7 @@ mod tests {
         let mut arr = [
             5, 3, 8, 2, 1, 5, 7, 2, 6, 3, 0, 2, 4, 6, 8, 9, 9, 1, 5, 4, 3, 2, 4,
         ];
-        sort_by_predicate(&mut arr, |a, b| a < b, Partition::First);
+        sort_by_predicate(&mut arr, |a, b| a < b, Partition::First, Scheme::Lomuto);
         assert_eq!(
             [0, 1, 1, 2, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 7, 8, 8, 9, 9],
             arr
@@ -108,7 +286,37 @@ mod tests {
         let car3 = Car { age: 35 };
 
         let mut vec = vec![car1, car2, car3];
-        sort_by_predicate(&mut vec, |c1, c2| c1.age < c2.age, Partition::First);
+        sort_by_predicate(
+            &mut vec,
+            |c1, c2| c1.age < c2.age,
+            Partition::First,
+            Scheme::Lomuto,
+        );
         assert_eq!(vec![car2, car1, car3], vec);
     }
+
+    #[test]
+    fn sort_with_custom_pivot_strategy() {
+        struct SecondToLast;
+
+        impl PivotStrategy for SecondToLast {
+            fn select_pivot<T, P>(&self, _: &mut [T], l: usize, r: usize, _: &P) -> usize
+            where
+                P: Fn(&T, &T) -> bool,
+            {
+                r.saturating_sub(1).max(l)
+            }
+        }
+
+        let less = |a: &u32, b: &u32| a < b;
+        assert_eq!(SecondToLast.select_pivot(&mut [3, 1, 2], 0, 2, &less), 1);
+        assert_eq!(SecondToLast.select_pivot(&mut [1], 0, 0, &less), 0);
+
+        let mut expected = random_vec(1000, 139);
+        expected.sort();
+
+        let mut vec = random_vec(1000, 139);
+        sort(&mut vec, Order::Asc, SecondToLast, Scheme::Lomuto);
+        assert_eq!(expected, vec);
+    }
 }
@@ -0,0 +1,222 @@
+use crate::algorithms::insertion_sort;
+use crate::Order;
+
+const INSERTION_THRESHOLD: usize = 16;
+const SAMPLE_THRESHOLD: usize = 600;
+
+// Same contract as `select_nth`, but in O(n) comparisons for any input.
+pub fn select_nth_deterministic<T: PartialOrd>(data: &mut [T], k: usize, order: Order) {
+    let predicate = match order {
+        Order::Asc => |a: &T, b: &T| a < b,
+        Order::Desc => |a: &T, b: &T| a > b,
+    };
+    select_nth_deterministic_by_predicate(data, k, predicate);
+}
+
+pub fn select_nth_deterministic_by_predicate<T, P>(data: &mut [T], k: usize, predicate: P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    assert!(
+        k < data.len(),
+        "index {k} out of bounds for length {}",
+        data.len()
+    );
+    select(data, k, &predicate);
+}
+
+fn select<T, P>(data: &mut [T], k: usize, predicate: &P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let (mut l, mut r) = (0, data.len() - 1);
+
+    // Floyd–Rivest picks a pivot very close to the k-th element from a small
+    // sample. Once it fails to discard at least a quarter of the range the
+    // rest of the work is left to median of medians, which bounds the total.
+    let mut sampling = true;
+
+    while r - l >= INSERTION_THRESHOLD {
+        let len = r - l + 1;
+        let pivot = if sampling && len > SAMPLE_THRESHOLD {
+            floyd_rivest_pivot(data, l, r, k, predicate)
+        } else {
+            median_of_medians(data, l, r, predicate)
+        };
+
+        let (lt, gt) = partition_around(data, l, r, pivot, k, predicate);
+        if k < lt {
+            r = lt - 1;
+        } else if k >= gt {
+            l = gt;
+        } else {
+            return;
+        }
+
+        if 4 * (r - l + 1) > 3 * len {
+            sampling = false;
+        }
+    }
+
+    insertion_sort::sort_by_predicate(&mut data[l..=r], predicate);
+}
+
+// Selects the k-th element of a sample around `k` whose size is about
+// n^(2/3) and returns `k`, where that element now is.
+fn floyd_rivest_pivot<T, P>(data: &mut [T], l: usize, r: usize, k: usize, predicate: &P) -> usize
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let n = (r - l + 1) as f64;
+    let i = (k - l + 1) as f64;
+    let z = n.ln();
+    let s = 0.5 * (2.0 * z / 3.0).exp();
+    let sd = 0.5 * (z * s * (n - s) / n).sqrt() * (i - n / 2.0).signum();
+
+    let sample_l = (k as f64 - i * s / n + sd).max(l as f64) as usize;
+    let sample_r = (k as f64 + (n - i) * s / n + sd).min(r as f64) as usize;
+    select(&mut data[sample_l..=sample_r], k - sample_l, predicate);
+    k
+}
+
+// Moves the median of every group of five to the front of the range and
+// returns the index of the median of those medians, which is guaranteed to
+// have at least 3/10 of the range on either side.
+pub(crate) fn median_of_medians<T, P>(data: &mut [T], l: usize, r: usize, predicate: &P) -> usize
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let mut medians = 0;
+    for start in (l..=r).step_by(5) {
+        let end = (start + 5).min(r + 1);
+        insertion_sort::sort_by_predicate(&mut data[start..end], predicate);
+        data.swap(l + medians, start + (end - start - 1) / 2);
+        medians += 1;
+    }
+
+    let mid = (medians - 1) / 2;
+    select(&mut data[l..l + medians], mid, predicate);
+    l + mid
+}
+
+// Partitions the range around `data[pivot]` and returns the range `[lt, gt)`
+// it ends up in. Elements equal to the pivot are only gathered next to it
+// when `k` is on their side, otherwise they go with the greater ones.
+//
+// This does not go through `partition_by_predicate`, which calls back into
+// `median_of_medians` for `Partition::MedianOfMedians`.
+fn partition_around<T, P>(
+    data: &mut [T],
+    l: usize,
+    r: usize,
+    pivot: usize,
+    k: usize,
+    predicate: &P,
+) -> (usize, usize)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    data.swap(l, pivot);
+    let mut lt = l;
+    for j in l + 1..=r {
+        if predicate(&data[j], &data[l]) {
+            lt += 1;
+            data.swap(lt, j);
+        }
+    }
+    data.swap(l, lt);
+
+    let mut gt = lt + 1;
+    if k > lt {
+        for j in lt + 1..=r {
+            if !predicate(&data[lt], &data[j]) {
+                data.swap(gt, j);
+                gt += 1;
+            }
+        }
+    }
+    (lt, gt)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::helpers::{random_vec, Car};
+
+    fn assert_selected<T: PartialOrd>(data: &[T], k: usize) {
+        assert!(data[..k].iter().all(|x| x <= &data[k]));
+        assert!(data[k + 1..].iter().all(|x| x >= &data[k]));
+    }
+
+    #[test]
+    fn select_small() {
+        let mut arr = [3, 2, 4, 2, 5, 7, 1, 0, 6];
+        select_nth_deterministic(&mut arr, 4, Order::Asc);
+        assert_eq!(arr[4], 3);
+        assert_selected(&arr, 4);
+
+        let mut arr = [3, 2, 4, 2, 5, 7, 1, 0, 6];
+        select_nth_deterministic(&mut arr, 0, Order::Desc);
+        assert_eq!(arr[0], 7);
+    }
+
+    #[test]
+    fn select_random_vector() {
+        let mut expected = random_vec(20_000, 103);
+        expected.sort();
+
+        for k in [0, 17, 5000, 10_000, 19_999] {
+            let mut vec = random_vec(20_000, 103);
+            select_nth_deterministic(&mut vec, k, Order::Asc);
+            assert_eq!(vec[k], expected[k]);
+            assert_selected(&vec, k);
+        }
+    }
+
+    #[test]
+    fn median_of_medians_bound() {
+        let mut vec: Vec<u32> = (0..1000).rev().collect();
+        let pivot = median_of_medians(&mut vec, 0, 999, &|a, b| a < b);
+
+        let less = vec.iter().filter(|x| **x < vec[pivot]).count();
+        assert!((300..700).contains(&less));
+    }
+
+    #[test]
+    fn select_is_linear_on_adversarial_input() {
+        let len = 100_000;
+        let inputs: [Vec<u32>; 4] = [
+            (0..len).collect(),
+            (0..len).rev().collect(),
+            vec![7; len as usize],
+            (0..len).map(|x| x % 3).collect(),
+        ];
+
+        for input in inputs {
+            for k in [0, len as usize / 2, len as usize - 1] {
+                let mut expected = input.clone();
+                expected.sort();
+
+                let comparisons = Cell::new(0);
+                let mut vec = input.clone();
+                select_nth_deterministic_by_predicate(&mut vec, k, |a, b| {
+                    comparisons.set(comparisons.get() + 1);
+                    a < b
+                });
+
+                assert_eq!(vec[k], expected[k]);
+                assert_selected(&vec, k);
+                assert!(comparisons.get() < 30 * len as usize);
+            }
+        }
+    }
+
+    #[test]
+    fn select_by_predicate() {
+        let mut vec = vec![Car { age: 25 }, Car { age: 15 }, Car { age: 35 }];
+        select_nth_deterministic_by_predicate(&mut vec, 2, |c1, c2| c1.age < c2.age);
+        assert_eq!(vec[2], Car { age: 35 });
+    }
+}
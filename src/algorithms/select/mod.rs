@@ -0,0 +1,5 @@
+mod deterministic;
+mod select;
+
+pub use deterministic::*;
+pub use select::*;
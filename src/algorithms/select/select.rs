@@ -0,0 +1,151 @@
+use std::cmp::Ordering;
+
+use crate::algorithms::quick_sort::{partition_by_predicate, PivotStrategy};
+use crate::Order;
+
+// Reorders the slice so that `data[k]` is the element that would end up there
+// after sorting, with no element before it greater and no element after it
+// less. Panics if `k` is out of bounds.
+pub fn select_nth<T: PartialOrd, S: PivotStrategy>(
+    data: &mut [T],
+    k: usize,
+    order: Order,
+    partition: S,
+) {
+    let predicate = match order {
+        Order::Asc => |a: &T, b: &T| a < b,
+        Order::Desc => |a: &T, b: &T| a > b,
+    };
+    select_nth_by_predicate(data, k, predicate, partition);
+}
+
+pub fn select_nth_by_predicate<T, P, S>(data: &mut [T], k: usize, predicate: P, partition: S)
+where
+    P: Fn(&T, &T) -> bool,
+    S: PivotStrategy,
+{
+    assert!(
+        k < data.len(),
+        "index {k} out of bounds for length {}",
+        data.len()
+    );
+
+    let (mut l, mut r) = (0, data.len() - 1);
+    while l < r {
+        let pivot = partition_by_predicate(data, l, r, &predicate, &partition);
+        match k.cmp(&pivot) {
+            Ordering::Less => r = pivot - 1,
+            Ordering::Equal => return,
+            Ordering::Greater => {
+                // Keys equal to the pivot all end up after it, gather them
+                // next to it so a long run of them is discarded at once.
+                let gt = gather_equal(data, pivot, r, &predicate);
+                if k < gt {
+                    return;
+                }
+                l = gt;
+            }
+        }
+    }
+}
+
+// Moves the elements of `data[pivot + 1..=r]` equal to `data[pivot]` right
+// behind it and returns the end of that run.
+fn gather_equal<T, P>(data: &mut [T], pivot: usize, r: usize, predicate: &P) -> usize
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let mut gt = pivot + 1;
+    for j in pivot + 1..=r {
+        if !predicate(&data[pivot], &data[j]) {
+            data.swap(gt, j);
+            gt += 1;
+        }
+    }
+    gt
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::helpers::{random_vec, Car};
+    use crate::Partition;
+
+    fn assert_selected<T: PartialOrd>(data: &[T], k: usize) {
+        assert!(data[..k].iter().all(|x| x <= &data[k]));
+        assert!(data[k + 1..].iter().all(|x| x >= &data[k]));
+    }
+
+    #[test]
+    fn select_median() {
+        let mut arr = [3, 2, 4, 2, 5, 7, 1, 0, 6];
+        select_nth(&mut arr, 4, Order::Asc, Partition::Center);
+        assert_eq!(arr[4], 3);
+        assert_selected(&arr, 4);
+
+        let mut arr = [3, 2, 4, 2, 5, 7, 1, 0, 6];
+        select_nth(&mut arr, 1, Order::Desc, Partition::First);
+        assert_eq!(arr[1], 6);
+    }
+
+    #[test]
+    fn select_random_vector() {
+        let mut expected = random_vec(5000, 101);
+        expected.sort();
+
+        for (k, partition) in [
+            (0, Partition::First),
+            (2500, Partition::Center),
+            (4999, Partition::Last),
+            (4750, Partition::Ninther),
+            (1234, Partition::Random(3)),
+            (3000, Partition::MedianOfMedians),
+        ] {
+            let mut vec = random_vec(5000, 101);
+            select_nth(&mut vec, k, Order::Asc, partition);
+            assert_eq!(vec[k], expected[k]);
+            assert_selected(&vec, k);
+        }
+    }
+
+    #[test]
+    fn select_duplicates_is_linear() {
+        let len = 100_000;
+        for distinct in [1, 3] {
+            let input: Vec<u32> = (0..len).map(|x| x % distinct).collect();
+            let mut expected = input.clone();
+            expected.sort();
+
+            for k in [0, len as usize / 2, len as usize - 1] {
+                for partition in [Partition::Center, Partition::Random(1), Partition::Ninther] {
+                    let comparisons = Cell::new(0);
+                    let mut vec = input.clone();
+                    let predicate = |a: &u32, b: &u32| {
+                        comparisons.set(comparisons.get() + 1);
+                        a < b
+                    };
+                    select_nth_by_predicate(&mut vec, k, predicate, partition);
+
+                    assert_eq!(vec[k], expected[k]);
+                    assert_selected(&vec, k);
+                    assert!(comparisons.get() < 10 * len as usize);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn select_by_predicate() {
+        let mut vec = vec![Car { age: 25 }, Car { age: 15 }, Car { age: 35 }];
+        select_nth_by_predicate(&mut vec, 0, |c1, c2| c1.age < c2.age, Partition::Last);
+        assert_eq!(vec[0], Car { age: 15 });
+    }
+
+    #[test]
+    #[should_panic]
+    fn select_out_of_bounds() {
+        select_nth(&mut [1, 2, 3], 3, Order::Asc, Partition::First);
+    }
+}
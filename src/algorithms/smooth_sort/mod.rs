@@ -0,0 +1,3 @@
+mod smooth_sort;
+
+pub use smooth_sort::*;
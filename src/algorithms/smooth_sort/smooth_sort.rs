@@ -0,0 +1,220 @@
+use crate::Order;
+
+const LEONARDO: [usize; 64] = leonardo_numbers();
+
+const fn leonardo_numbers() -> [usize; 64] {
+    let mut numbers = [1; 64];
+    let mut i = 2;
+    while i < 64 {
+        numbers[i] = numbers[i - 1] + numbers[i - 2] + 1;
+        i += 1;
+    }
+    numbers
+}
+
+pub fn sort<T: PartialOrd>(data: &mut [T], order: Order) {
+    let predicate = match order {
+        Order::Asc => |a: &T, b: &T| a < b,
+        Order::Desc => |a: &T, b: &T| a > b,
+    };
+    sort_by_predicate(data, predicate);
+}
+
+pub fn sort_by_predicate<T, P>(data: &mut [T], predicate: P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    // Bit k of `trees` is set when the forest contains a Leonardo tree of
+    // order k. Orders strictly decrease from left to right, so the lowest set
+    // bit is always the rightmost tree.
+    let mut trees: u64 = 0;
+
+    for i in 0..data.len() {
+        let last = trees.trailing_zeros();
+        if trees != 0 && trees & (1 << (last + 1)) != 0 {
+            trees &= !(0b11 << last);
+            trees |= 1 << (last + 2);
+        } else if last == 1 {
+            trees |= 1;
+        } else {
+            trees |= 0b10;
+        }
+
+        // A tree that is going to become a subtree of a bigger one later only
+        // needs to be a heap, its root is compared with the others then.
+        let order = trees.trailing_zeros();
+        let remaining = data.len() - i - 1;
+        let merged_later = if trees & (1 << (order + 1)) != 0 {
+            remaining > 0
+        } else {
+            remaining > LEONARDO[order.max(1) as usize - 1]
+        };
+        if merged_later {
+            sift_down(data, i, order, &predicate);
+        } else {
+            rectify(data, i, order, trees, &predicate);
+        }
+    }
+
+    for i in (0..data.len()).rev() {
+        let last = trees.trailing_zeros();
+        trees &= !(1 << last);
+        if last <= 1 {
+            continue;
+        }
+
+        trees |= 0b11 << (last - 2);
+        let left = i - 1 - LEONARDO[last as usize - 2];
+        rectify(data, left, last - 1, trees, &predicate);
+        rectify(data, i - 1, last - 2, trees, &predicate);
+    }
+}
+
+// Moves the root of the tree of order `order` ending at `root` to the left
+// past every tree whose root is larger, then sifts it down in the tree it
+// lands in. Only the trees in `trees` with a higher order are considered.
+fn rectify<T, P>(data: &mut [T], mut root: usize, mut order: u32, trees: u64, predicate: &P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    loop {
+        let higher = trees >> (order + 1);
+        if higher == 0 {
+            break;
+        }
+
+        let previous = root - LEONARDO[order as usize];
+        let mut max = root;
+        if order >= 2 {
+            for child in children(root, order).map(|(child, _)| child) {
+                if predicate(&data[max], &data[child]) {
+                    max = child;
+                }
+            }
+        }
+        if !predicate(&data[max], &data[previous]) {
+            break;
+        }
+
+        data.swap(root, previous);
+        root = previous;
+        order += 1 + higher.trailing_zeros();
+    }
+
+    sift_down(data, root, order, predicate);
+}
+
+fn sift_down<T, P>(data: &mut [T], mut root: usize, mut order: u32, predicate: &P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    while order >= 2 {
+        let [(left, left_order), (right, right_order)] = children(root, order);
+        let (child, child_order) = if predicate(&data[left], &data[right]) {
+            (right, right_order)
+        } else {
+            (left, left_order)
+        };
+
+        if !predicate(&data[root], &data[child]) {
+            break;
+        }
+        data.swap(root, child);
+        root = child;
+        order = child_order;
+    }
+}
+
+fn children(root: usize, order: u32) -> [(usize, u32); 2] {
+    let right = root - 1;
+    let left = right - LEONARDO[order as usize - 2];
+    [(left, order - 1), (right, order - 2)]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::helpers::{random_vec, Car, Person};
+
+    #[test]
+    fn sort_array_asc() {
+        let mut arr = [2, 3, 1, 5, 4];
+        sort(&mut arr, Order::Asc);
+        assert_eq!([1, 2, 3, 4, 5], arr);
+
+        let mut arr = [
+            5, 3, 8, 2, 1, 5, 7, 2, 6, 3, 0, 2, 4, 6, 8, 9, 9, 1, 5, 4, 3, 2, 4,
+        ];
+        sort(&mut arr, Order::Asc);
+        assert_eq!(
+            [0, 1, 1, 2, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 7, 8, 8, 9, 9],
+            arr
+        );
+    }
+
+    #[test]
+    fn sort_array_desc() {
+        let mut arr = [2, 3, 1, 5, 4];
+        sort(&mut arr, Order::Desc);
+        assert_eq!([5, 4, 3, 2, 1], arr);
+    }
+
+    #[test]
+    fn sort_random_vector() {
+        let mut expected = random_vec(5000, 37);
+        expected.sort();
+
+        let mut vec = random_vec(5000, 37);
+        sort(&mut vec, Order::Asc);
+        assert_eq!(expected, vec);
+    }
+
+    #[test]
+    fn sort_sorted_is_linear() {
+        let comparisons = Cell::new(0);
+        let mut vec: Vec<u32> = (0..10_000).collect();
+        sort_by_predicate(&mut vec, |a, b| {
+            comparisons.set(comparisons.get() + 1);
+            a < b
+        });
+
+        assert_eq!((0..10_000).collect::<Vec<u32>>(), vec);
+        assert!(comparisons.get() < 5 * vec.len());
+    }
+
+    #[test]
+    fn sort_struct_vector() {
+        let person1 = Person::new(25);
+        let person2 = Person::new(15);
+        let person3 = Person::new(35);
+
+        let mut vec = vec![person1, person2, person3];
+        sort(&mut vec, Order::Asc);
+        assert_eq!(vec![person2, person1, person3], vec);
+    }
+
+    #[test]
+    fn sort_array_by_predicate() {
+        let mut arr = [
+            5, 3, 8, 2, 1, 5, 7, 2, 6, 3, 0, 2, 4, 6, 8, 9, 9, 1, 5, 4, 3, 2, 4,
+        ];
+        sort_by_predicate(&mut arr, |a, b| a < b);
+        assert_eq!(
+            [0, 1, 1, 2, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 7, 8, 8, 9, 9],
+            arr
+        );
+    }
+
+    #[test]
+    fn sort_struct_vector_by_predicate() {
+        let car1 = Car { age: 25 };
+        let car2 = Car { age: 15 };
+        let car3 = Car { age: 35 };
+
+        let mut vec = vec![car1, car2, car3];
+        sort_by_predicate(&mut vec, |c1, c2| c1.age < c2.age);
+        assert_eq!(vec![car2, car1, car3], vec);
+    }
+}
@@ -0,0 +1,296 @@
+use std::mem;
+
+pub const MIN_GALLOP: usize = 7;
+
+pub fn merge_by_predicate<T, P>(
+    data: &mut [T],
+    mid: usize,
+    predicate: &P,
+    buffer: &mut Vec<T>,
+    min_gallop: &mut usize,
+) where
+    T: Clone,
+    P: Fn(&T, &T) -> bool,
+{
+    if mid == 0 || mid == data.len() {
+        return;
+    }
+
+    // Elements of the first run that are not greater than the head of the
+    // second one, and elements of the second run that are not smaller than
+    // the tail of the first one, are already in place.
+    let start = gallop_right(&data[mid], &data[..mid], false, predicate);
+    if start == mid {
+        return;
+    }
+    let end = mid + gallop_left(&data[mid - 1], &data[mid..], true, predicate);
+
+    let data = &mut data[start..end];
+    let mid = mid - start;
+    if mid <= data.len() - mid {
+        merge_lo(data, mid, predicate, buffer, min_gallop);
+    } else {
+        merge_hi(data, mid, predicate, buffer, min_gallop);
+    }
+}
+
+fn merge_lo<T, P>(
+    data: &mut [T],
+    mid: usize,
+    predicate: &P,
+    buffer: &mut Vec<T>,
+    min_gallop: &mut usize,
+) where
+    T: Clone,
+    P: Fn(&T, &T) -> bool,
+{
+    buffer.clear();
+    buffer.extend_from_slice(&data[..mid]);
+
+    // data[k..j] always holds exactly the elements still waiting in the
+    // buffer, so whatever is taken next is swapped into position k.
+    let len = data.len();
+    let (mut i, mut j, mut k) = (0, mid, 0);
+
+    'merge: loop {
+        let (mut count1, mut count2) = (0, 0);
+        while count1 < *min_gallop && count2 < *min_gallop {
+            if predicate(&data[j], &buffer[i]) {
+                data.swap(k, j);
+                j += 1;
+                count1 = 0;
+                count2 += 1;
+            } else {
+                mem::swap(&mut data[k], &mut buffer[i]);
+                i += 1;
+                count1 += 1;
+                count2 = 0;
+            }
+            k += 1;
+            if i == buffer.len() || j == len {
+                break 'merge;
+            }
+        }
+
+        loop {
+            let count1 = gallop_right(&data[j], &buffer[i..], false, predicate);
+            data[k..k + count1].swap_with_slice(&mut buffer[i..i + count1]);
+            i += count1;
+            k += count1;
+            if i == buffer.len() {
+                break 'merge;
+            }
+            data.swap(k, j);
+            j += 1;
+            k += 1;
+            if j == len {
+                break 'merge;
+            }
+
+            let count2 = gallop_left(&buffer[i], &data[j..], false, predicate);
+            for t in 0..count2 {
+                data.swap(k + t, j + t);
+            }
+            j += count2;
+            k += count2;
+            if j == len {
+                break 'merge;
+            }
+            mem::swap(&mut data[k], &mut buffer[i]);
+            i += 1;
+            k += 1;
+            if i == buffer.len() {
+                break 'merge;
+            }
+
+            *min_gallop = min_gallop.saturating_sub(1).max(1);
+            if count1 < MIN_GALLOP && count2 < MIN_GALLOP {
+                break;
+            }
+        }
+        *min_gallop += 2;
+    }
+
+    let rest = buffer.len() - i;
+    data[k..k + rest].swap_with_slice(&mut buffer[i..]);
+}
+
+fn merge_hi<T, P>(
+    data: &mut [T],
+    mid: usize,
+    predicate: &P,
+    buffer: &mut Vec<T>,
+    min_gallop: &mut usize,
+) where
+    T: Clone,
+    P: Fn(&T, &T) -> bool,
+{
+    buffer.clear();
+    buffer.extend_from_slice(&data[mid..]);
+
+    // Mirror image of merge_lo: data[n1..n1 + n2] holds exactly the elements
+    // still waiting in the buffer and the merge fills it from the back.
+    let (mut n1, mut n2) = (mid, buffer.len());
+
+    'merge: loop {
+        let (mut count1, mut count2) = (0, 0);
+        while count1 < *min_gallop && count2 < *min_gallop {
+            if predicate(&buffer[n2 - 1], &data[n1 - 1]) {
+                data.swap(n1 - 1, n1 + n2 - 1);
+                n1 -= 1;
+                count1 += 1;
+                count2 = 0;
+            } else {
+                mem::swap(&mut data[n1 + n2 - 1], &mut buffer[n2 - 1]);
+                n2 -= 1;
+                count1 = 0;
+                count2 += 1;
+            }
+            if n1 == 0 || n2 == 0 {
+                break 'merge;
+            }
+        }
+
+        loop {
+            let count1 = n1 - gallop_right(&buffer[n2 - 1], &data[..n1], true, predicate);
+            for t in 0..count1 {
+                data.swap(n1 - 1 - t, n1 + n2 - 1 - t);
+            }
+            n1 -= count1;
+            if n1 == 0 {
+                break 'merge;
+            }
+            mem::swap(&mut data[n1 + n2 - 1], &mut buffer[n2 - 1]);
+            n2 -= 1;
+            if n2 == 0 {
+                break 'merge;
+            }
+
+            let count2 = n2 - gallop_left(&data[n1 - 1], &buffer[..n2], true, predicate);
+            data[n1 + n2 - count2..n1 + n2].swap_with_slice(&mut buffer[n2 - count2..n2]);
+            n2 -= count2;
+            if n2 == 0 {
+                break 'merge;
+            }
+            data.swap(n1 - 1, n1 + n2 - 1);
+            n1 -= 1;
+            if n1 == 0 {
+                break 'merge;
+            }
+
+            *min_gallop = min_gallop.saturating_sub(1).max(1);
+            if count1 < MIN_GALLOP && count2 < MIN_GALLOP {
+                break;
+            }
+        }
+        *min_gallop += 2;
+    }
+
+    data[n1..n1 + n2].swap_with_slice(&mut buffer[..n2]);
+}
+
+pub fn gallop_left<T, P>(key: &T, run: &[T], from_end: bool, predicate: &P) -> usize
+where
+    P: Fn(&T, &T) -> bool,
+{
+    gallop(run.len(), from_end, |i| !predicate(&run[i], key))
+}
+
+pub fn gallop_right<T, P>(key: &T, run: &[T], from_end: bool, predicate: &P) -> usize
+where
+    P: Fn(&T, &T) -> bool,
+{
+    gallop(run.len(), from_end, |i| predicate(key, &run[i]))
+}
+
+fn gallop<F>(len: usize, from_end: bool, is_past: F) -> usize
+where
+    F: Fn(usize) -> bool,
+{
+    if from_end {
+        len - exponential_search(len, |i| !is_past(len - 1 - i))
+    } else {
+        exponential_search(len, is_past)
+    }
+}
+
+fn exponential_search<F>(len: usize, is_past: F) -> usize
+where
+    F: Fn(usize) -> bool,
+{
+    if len == 0 || is_past(0) {
+        return 0;
+    }
+
+    let mut last = 0;
+    let mut offset = 1;
+    while offset < len && !is_past(offset) {
+        last = offset;
+        offset = offset * 2 + 1;
+    }
+
+    let (mut lo, mut hi) = (last + 1, offset.min(len));
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if is_past(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    lo
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gallop_test() {
+        let arr = [1, 2, 2, 2, 3, 5, 8, 8, 9];
+        assert_eq!(gallop_left(&2, &arr, false, &|a, b| a < b), 1);
+        assert_eq!(gallop_right(&2, &arr, false, &|a, b| a < b), 4);
+        assert_eq!(gallop_left(&8, &arr, true, &|a, b| a < b), 6);
+        assert_eq!(gallop_right(&8, &arr, true, &|a, b| a < b), 8);
+        assert_eq!(gallop_left(&0, &arr, false, &|a, b| a < b), 0);
+        assert_eq!(gallop_right(&10, &arr, true, &|a, b| a < b), 9);
+    }
+
+    #[test]
+    fn merge_by_predicate_test() {
+        let mut min_gallop = MIN_GALLOP;
+
+        let mut arr = [1, 4, 6, 9, 2, 3, 6, 10];
+        merge_by_predicate(&mut arr, 4, &|a, b| a < b, &mut Vec::new(), &mut min_gallop);
+        assert_eq!(arr, [1, 2, 3, 4, 6, 6, 9, 10]);
+
+        let mut arr = [1, 4, 6, 7, 8, 9, 2, 3];
+        merge_by_predicate(&mut arr, 6, &|a, b| a < b, &mut Vec::new(), &mut min_gallop);
+        assert_eq!(arr, [1, 2, 3, 4, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn merge_is_stable() {
+        let mut min_gallop = MIN_GALLOP;
+
+        let mut arr = [(1, 'a'), (2, 'a'), (2, 'b'), (1, 'b'), (2, 'c')];
+        merge_by_predicate(
+            &mut arr,
+            3,
+            &|a, b| a.0 < b.0,
+            &mut Vec::new(),
+            &mut min_gallop,
+        );
+        assert_eq!(arr, [(1, 'a'), (1, 'b'), (2, 'a'), (2, 'b'), (2, 'c')]);
+
+        let mut arr = [(1, 'a'), (2, 'a'), (1, 'b'), (1, 'c'), (2, 'b')];
+        merge_by_predicate(
+            &mut arr,
+            2,
+            &|a, b| a.0 < b.0,
+            &mut Vec::new(),
+            &mut min_gallop,
+        );
+        assert_eq!(arr, [(1, 'a'), (1, 'b'), (1, 'c'), (2, 'a'), (2, 'b')]);
+    }
+}
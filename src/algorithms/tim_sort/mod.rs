@@ -0,0 +1,5 @@
+pub(crate) mod merge;
+pub(crate) mod run;
+mod tim_sort;
+
+pub use tim_sort::*;
@@ -0,0 +1,82 @@
+pub fn min_run_length(mut len: usize) -> usize {
+    let mut r = 0;
+    while len >= 64 {
+        r |= len & 1;
+        len >>= 1;
+    }
+    len + r
+}
+
+pub fn find_run_by_predicate<T, P>(data: &mut [T], predicate: &P) -> usize
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let len = data.len();
+    if len < 2 {
+        return len;
+    }
+
+    let mut end = 2;
+    if predicate(&data[1], &data[0]) {
+        // Only strictly descending runs may be reversed, otherwise equal
+        // elements would swap places and the sort would not be stable.
+        while end < len && predicate(&data[end], &data[end - 1]) {
+            end += 1;
+        }
+        data[..end].reverse();
+    } else {
+        while end < len && !predicate(&data[end], &data[end - 1]) {
+            end += 1;
+        }
+    }
+    end
+}
+
+pub fn binary_insertion_sort_by_predicate<T, P>(data: &mut [T], sorted: usize, predicate: &P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    for i in sorted.max(1)..data.len() {
+        let (mut lo, mut hi) = (0, i);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if predicate(&data[i], &data[mid]) {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        data[lo..=i].rotate_right(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_run_length_test() {
+        assert_eq!(min_run_length(63), 63);
+        assert_eq!(min_run_length(64), 32);
+        assert_eq!(min_run_length(65), 33);
+        assert_eq!(min_run_length(1000), 63);
+    }
+
+    #[test]
+    fn find_run_by_predicate_test() {
+        let mut arr = [1, 2, 2, 5, 3, 4];
+        assert_eq!(find_run_by_predicate(&mut arr, &|a, b| a < b), 4);
+        assert_eq!(arr, [1, 2, 2, 5, 3, 4]);
+
+        let mut arr = [5, 4, 2, 2, 3];
+        assert_eq!(find_run_by_predicate(&mut arr, &|a, b| a < b), 3);
+        assert_eq!(arr, [2, 4, 5, 2, 3]);
+    }
+
+    #[test]
+    fn binary_insertion_sort_by_predicate_test() {
+        let mut arr = [(1, 'a'), (3, 'a'), (2, 'a'), (1, 'b'), (3, 'b')];
+        binary_insertion_sort_by_predicate(&mut arr, 2, &|a, b| a.0 < b.0);
+        assert_eq!(arr, [(1, 'a'), (1, 'b'), (2, 'a'), (3, 'a'), (3, 'b')]);
+    }
+}
@@ -0,0 +1,200 @@
+use super::merge::{merge_by_predicate, MIN_GALLOP};
+use super::run::{binary_insertion_sort_by_predicate, find_run_by_predicate, min_run_length};
+use crate::Order;
+
+struct Run {
+    start: usize,
+    len: usize,
+}
+
+pub fn sort<T: PartialOrd + Clone>(data: &mut [T], order: Order) {
+    let predicate = match order {
+        Order::Asc => |a: &T, b: &T| a < b,
+        Order::Desc => |a: &T, b: &T| a > b,
+    };
+    sort_by_predicate(data, predicate);
+}
+
+pub fn sort_by_predicate<T, P>(data: &mut [T], predicate: P)
+where
+    T: Clone,
+    P: Fn(&T, &T) -> bool,
+{
+    let len = data.len();
+    if len < 2 {
+        return;
+    }
+
+    let min_run = min_run_length(len);
+    let mut runs = Vec::new();
+    let mut buffer = Vec::new();
+    let mut min_gallop = MIN_GALLOP;
+
+    let mut start = 0;
+    while start < len {
+        let mut end = start + find_run_by_predicate(&mut data[start..], &predicate);
+        if end - start < min_run {
+            let forced_end = (start + min_run).min(len);
+            binary_insertion_sort_by_predicate(
+                &mut data[start..forced_end],
+                end - start,
+                &predicate,
+            );
+            end = forced_end;
+        }
+
+        runs.push(Run {
+            start,
+            len: end - start,
+        });
+        while let Some(i) = collapse_index(&runs) {
+            merge_at(data, &mut runs, i, &predicate, &mut buffer, &mut min_gallop);
+        }
+
+        start = end;
+    }
+
+    while runs.len() > 1 {
+        let n = runs.len();
+        let i = if n >= 3 && runs[n - 3].len < runs[n - 1].len {
+            n - 3
+        } else {
+            n - 2
+        };
+        merge_at(data, &mut runs, i, &predicate, &mut buffer, &mut min_gallop);
+    }
+}
+
+// Keeps the run lengths on the stack growing at least as fast as the
+// Fibonacci numbers, which bounds the stack to O(log n) runs and keeps the
+// merges balanced. The invariant is checked on the top four runs, not three.
+fn collapse_index(runs: &[Run]) -> Option<usize> {
+    let n = runs.len();
+    if n < 2 {
+        return None;
+    }
+
+    if (n >= 3 && runs[n - 3].len <= runs[n - 2].len + runs[n - 1].len)
+        || (n >= 4 && runs[n - 4].len <= runs[n - 3].len + runs[n - 2].len)
+    {
+        if runs[n - 3].len < runs[n - 1].len {
+            Some(n - 3)
+        } else {
+            Some(n - 2)
+        }
+    } else if runs[n - 2].len <= runs[n - 1].len {
+        Some(n - 2)
+    } else {
+        None
+    }
+}
+
+fn merge_at<T, P>(
+    data: &mut [T],
+    runs: &mut Vec<Run>,
+    i: usize,
+    predicate: &P,
+    buffer: &mut Vec<T>,
+    min_gallop: &mut usize,
+) where
+    T: Clone,
+    P: Fn(&T, &T) -> bool,
+{
+    let (start, mid) = (runs[i].start, runs[i].len);
+    let end = runs[i + 1].start + runs[i + 1].len;
+
+    merge_by_predicate(&mut data[start..end], mid, predicate, buffer, min_gallop);
+
+    runs[i].len = end - start;
+    runs.remove(i + 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{random_vec, Car, Person};
+
+    #[test]
+    fn sort_array_asc() {
+        let mut arr = [2, 3, 1, 5, 4];
+        sort(&mut arr, Order::Asc);
+        assert_eq!([1, 2, 3, 4, 5], arr);
+
+        let mut arr = [
+            5, 3, 8, 2, 1, 5, 7, 2, 6, 3, 0, 2, 4, 6, 8, 9, 9, 1, 5, 4, 3, 2, 4,
+        ];
+        sort(&mut arr, Order::Asc);
+        assert_eq!(
+            [0, 1, 1, 2, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 7, 8, 8, 9, 9],
+            arr
+        );
+    }
+
+    #[test]
+    fn sort_array_desc() {
+        let mut arr = [2, 3, 1, 5, 4];
+        sort(&mut arr, Order::Desc);
+        assert_eq!([5, 4, 3, 2, 1], arr);
+    }
+
+    #[test]
+    fn sort_natural_runs() {
+        let mut vec: Vec<u32> = (0..3000).collect();
+        vec.extend((0..2000).rev());
+        vec.extend(random_vec(1000, 13));
+        for i in (0..vec.len()).step_by(97) {
+            vec[i] = 500;
+        }
+
+        let mut expected = vec.clone();
+        expected.sort();
+
+        sort(&mut vec, Order::Asc);
+        assert_eq!(expected, vec);
+    }
+
+    #[test]
+    fn sort_is_stable() {
+        let keys = random_vec(5000, 17);
+        let mut expected: Vec<(u32, usize)> = keys.iter().map(|k| k % 10).zip(0..).collect();
+        expected.sort_by_key(|(k, _)| *k);
+
+        let mut vec: Vec<(u32, usize)> = keys.iter().map(|k| k % 10).zip(0..).collect();
+        sort_by_predicate(&mut vec, |a, b| a.0 < b.0);
+        assert_eq!(expected, vec);
+    }
+
+    #[test]
+    fn sort_struct_vector() {
+        let person1 = Person::new(25);
+        let person2 = Person::new(15);
+        let person3 = Person::new(35);
+
+        let mut vec = vec![person1, person2, person3];
+        sort(&mut vec, Order::Asc);
+        assert_eq!(vec![person2, person1, person3], vec);
+    }
+
+    #[test]
+    fn sort_array_by_predicate() {
+        let mut arr = [
+            5, 3, 8, 2, 1, 5, 7, 2, 6, 3, 0, 2, 4, 6, 8, 9, 9, 1, 5, 4, 3, 2, 4,
+        ];
+        sort_by_predicate(&mut arr, |a, b| a < b);
+        assert_eq!(
+            [0, 1, 1, 2, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 7, 8, 8, 9, 9],
+            arr
+        );
+    }
+
+    #[test]
+    fn sort_struct_vector_by_predicate() {
+        let car1 = Car { age: 25 };
+        let car2 = Car { age: 15 };
+        let car3 = Car { age: 35 };
+
+        let mut vec = vec![car1, car2, car3];
+        sort_by_predicate(&mut vec, |c1, c2| c1.age < c2.age);
+        assert_eq!(vec![car2, car1, car3], vec);
+    }
+}
@@ -0,0 +1,336 @@
+use std::cmp::Ordering;
+
+use crate::algorithms::heap_sort::heap::{down_heap_by_predicate, up_heap_by_predicate};
+use crate::Order;
+
+// A slot index together with the generation of the slot when the item was
+// pushed. Slots are reused once their item leaves the queue, and bumping the
+// generation then keeps old handles from referring to the new item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Handle {
+    slot: usize,
+    generation: u32,
+}
+
+// Items live in `items` for as long as they are queued and the heap itself
+// only stores their slots, so a handle stays valid while its item moves
+// around. `positions` maps every queued slot back to its place in `heap` and
+// `free` holds the slots that can be reused.
+//
+// `Order::Asc` pops the smallest item first and `Order::Desc` the largest.
+pub struct IndexedPriorityQueue<T: PartialOrd> {
+    heap: Vec<usize>,
+    items: Vec<Option<T>>,
+    positions: Vec<Option<usize>>,
+    generations: Vec<u32>,
+    free: Vec<usize>,
+    order: Order,
+}
+
+impl<T: PartialOrd> IndexedPriorityQueue<T> {
+    pub fn new(order: Order) -> Self {
+        IndexedPriorityQueue {
+            heap: Vec::new(),
+            items: Vec::new(),
+            positions: Vec::new(),
+            generations: Vec::new(),
+            free: Vec::new(),
+            order,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.position(handle).is_some()
+    }
+
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.position(handle)?;
+        self.items[handle.slot].as_ref()
+    }
+
+    pub fn peek(&self) -> Option<(Handle, &T)> {
+        let &top = self.heap.first()?;
+        self.items[top]
+            .as_ref()
+            .map(|item| (self.handle(top), item))
+    }
+
+    pub fn push(&mut self, item: T) -> Handle {
+        let i = self.heap.len();
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.items[slot] = Some(item);
+                self.positions[slot] = Some(i);
+                slot
+            }
+            None => {
+                self.items.push(Some(item));
+                self.positions.push(Some(i));
+                self.generations.push(0);
+                self.items.len() - 1
+            }
+        };
+        self.heap.push(slot);
+
+        self.sift_up(i);
+        self.handle(slot)
+    }
+
+    pub fn pop(&mut self) -> Option<(Handle, T)> {
+        let handle = self.handle(*self.heap.first()?);
+        self.remove(handle).map(|item| (handle, item))
+    }
+
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let i = self.position(handle)?;
+        let last = self.heap.len() - 1;
+
+        self.heap.swap(i, last);
+        self.positions[self.heap[i]] = Some(i);
+        self.heap.pop();
+        self.positions[handle.slot] = None;
+
+        if i < last {
+            self.sift(i);
+        }
+
+        self.generations[handle.slot] = self.generations[handle.slot].wrapping_add(1);
+        self.free.push(handle.slot);
+        self.items[handle.slot].take()
+    }
+
+    // Panics if `handle` is not queued or `item` is greater than its
+    // current value.
+    pub fn decrease_key(&mut self, handle: Handle, item: T) {
+        let current = self.get(handle).expect("handle is not in the queue");
+        assert!(
+            current.partial_cmp(&item) != Some(Ordering::Less),
+            "decrease_key called with a greater item"
+        );
+        self.replace(handle, item);
+    }
+
+    // Panics if `handle` is not queued or `item` is less than its current
+    // value.
+    pub fn increase_key(&mut self, handle: Handle, item: T) {
+        let current = self.get(handle).expect("handle is not in the queue");
+        assert!(
+            current.partial_cmp(&item) != Some(Ordering::Greater),
+            "increase_key called with a lesser item"
+        );
+        self.replace(handle, item);
+    }
+
+    fn handle(&self, slot: usize) -> Handle {
+        Handle {
+            slot,
+            generation: self.generations[slot],
+        }
+    }
+
+    fn position(&self, handle: Handle) -> Option<usize> {
+        if self.generations.get(handle.slot) != Some(&handle.generation) {
+            return None;
+        }
+        self.positions[handle.slot]
+    }
+
+    fn replace(&mut self, handle: Handle, item: T) {
+        self.items[handle.slot] = Some(item);
+        if let Some(i) = self.positions[handle.slot] {
+            self.sift(i);
+        }
+    }
+
+    fn sift(&mut self, i: usize) {
+        if self.sift_up(i) == i {
+            self.sift_down(i);
+        }
+    }
+
+    fn sift_up(&mut self, i: usize) -> usize {
+        let items = &self.items;
+        let predicate = predicate(&self.order);
+        let end = up_heap_by_predicate(&mut self.heap, i, &|a: &usize, b: &usize| {
+            predicate(&items[*a], &items[*b])
+        });
+
+        self.update_positions(i, end);
+        end
+    }
+
+    fn sift_down(&mut self, i: usize) -> usize {
+        let items = &self.items;
+        let predicate = predicate(&self.order);
+        let len = self.heap.len();
+        let end = down_heap_by_predicate(&mut self.heap, i, len, &|a: &usize, b: &usize| {
+            predicate(&items[*a], &items[*b])
+        });
+
+        self.update_positions(end, i);
+        end
+    }
+
+    // Every element the heap primitives moved lies on the path from `from`
+    // up to its ancestor `to`.
+    fn update_positions(&mut self, from: usize, to: usize) {
+        let mut j = from;
+        loop {
+            self.positions[self.heap[j]] = Some(j);
+            if j <= to {
+                break;
+            }
+            j = (j - 1) / 2;
+        }
+    }
+}
+
+// The heap primitives keep the greatest element at the root, so the queue
+// order is reversed to pop in `Order` direction.
+fn predicate<T: PartialOrd>(order: &Order) -> fn(&Option<T>, &Option<T>) -> bool {
+    match order {
+        Order::Asc => |a, b| a > b,
+        Order::Desc => |a, b| a < b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::random_vec;
+
+    fn drain<T: PartialOrd>(queue: &mut IndexedPriorityQueue<T>) -> Vec<T> {
+        let mut items = Vec::new();
+        while let Some((_, item)) = queue.pop() {
+            items.push(item);
+        }
+        items
+    }
+
+    #[test]
+    fn push_and_pop_in_order() {
+        let mut expected = random_vec(1000, 59);
+        expected.sort();
+
+        let mut queue = IndexedPriorityQueue::new(Order::Asc);
+        for item in random_vec(1000, 59) {
+            queue.push(item);
+        }
+        assert_eq!(queue.peek().map(|(_, item)| *item), Some(expected[0]));
+        assert_eq!(drain(&mut queue), expected);
+
+        let mut queue = IndexedPriorityQueue::new(Order::Desc);
+        for item in random_vec(1000, 59) {
+            queue.push(item);
+        }
+        expected.reverse();
+        assert_eq!(drain(&mut queue), expected);
+    }
+
+    #[test]
+    fn change_keys() {
+        let mut queue = IndexedPriorityQueue::new(Order::Asc);
+        let handles: Vec<Handle> = [50, 40, 30, 20, 10].map(|item| queue.push(item)).into();
+
+        queue.decrease_key(handles[0], 5);
+        assert_eq!(queue.peek(), Some((handles[0], &5)));
+
+        queue.increase_key(handles[4], 45);
+        queue.increase_key(handles[0], 35);
+        assert_eq!(queue.get(handles[4]), Some(&45));
+        assert_eq!(drain(&mut queue), [20, 30, 35, 40, 45]);
+    }
+
+    #[test]
+    fn remove_by_handle() {
+        let mut queue = IndexedPriorityQueue::new(Order::Desc);
+        let handles: Vec<Handle> = [3, 2, 4, 2, 5, 7, 1, 0, 6]
+            .map(|item| queue.push(item))
+            .into();
+
+        assert_eq!(queue.remove(handles[5]), Some(7));
+        assert_eq!(queue.remove(handles[1]), Some(2));
+        assert_eq!(queue.remove(handles[1]), None);
+        assert!(!queue.contains(handles[1]));
+        assert!(queue.contains(handles[0]));
+
+        assert_eq!(queue.pop(), Some((handles[8], 6)));
+        assert_eq!(drain(&mut queue), [5, 4, 3, 2, 1, 0]);
+        assert_eq!(queue.get(handles[0]), None);
+    }
+
+    #[test]
+    fn random_operations_match_sorted_order() {
+        let keys = random_vec(2000, 61);
+        let mut queue = IndexedPriorityQueue::new(Order::Asc);
+        let handles: Vec<Handle> = keys.iter().map(|&key| queue.push(key)).collect();
+
+        let mut expected = Vec::new();
+        for (i, &key) in keys.iter().enumerate() {
+            match i % 3 {
+                0 => {
+                    queue.remove(handles[i]);
+                }
+                1 => {
+                    queue.decrease_key(handles[i], key / 2);
+                    expected.push(key / 2);
+                }
+                _ => {
+                    queue.increase_key(handles[i], key + 1000);
+                    expected.push(key + 1000);
+                }
+            }
+        }
+        expected.sort();
+
+        assert_eq!(drain(&mut queue), expected);
+    }
+
+    #[test]
+    fn slots_are_reused() {
+        let mut queue = IndexedPriorityQueue::new(Order::Asc);
+        for item in random_vec(10, 163) {
+            queue.push(item);
+        }
+
+        for item in random_vec(10_000, 167) {
+            queue.push(item);
+            queue.pop();
+        }
+        assert_eq!(queue.len(), 10);
+        assert!(queue.items.len() <= 11);
+    }
+
+    #[test]
+    fn stale_handles_are_rejected() {
+        let mut queue = IndexedPriorityQueue::new(Order::Asc);
+        let old = queue.push(3);
+        assert_eq!(queue.pop(), Some((old, 3)));
+
+        let new = queue.push(5);
+        assert_ne!(old, new);
+        assert!(!queue.contains(old));
+        assert_eq!(queue.get(old), None);
+        assert_eq!(queue.remove(old), None);
+
+        assert!(queue.contains(new));
+        assert_eq!(queue.get(new), Some(&5));
+        assert_eq!(queue.remove(new), Some(5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn decrease_key_with_greater_item() {
+        let mut queue = IndexedPriorityQueue::new(Order::Asc);
+        let handle = queue.push(3);
+        queue.decrease_key(handle, 4);
+    }
+}
@@ -0,0 +1,3 @@
+mod indexed_priority_queue;
+
+pub use indexed_priority_queue::*;
@@ -0,0 +1,247 @@
+// Nodes on even levels are the least of their subtree and nodes on odd levels
+// the greatest, both with respect to `predicate`. The root is the minimum and
+// the maximum is one of its children.
+pub struct MinMaxHeap<T, P>
+where
+    P: Fn(&T, &T) -> bool,
+{
+    data: Vec<T>,
+    predicate: P,
+}
+
+impl<T, P> MinMaxHeap<T, P>
+where
+    P: Fn(&T, &T) -> bool,
+{
+    pub fn new(predicate: P) -> Self {
+        MinMaxHeap {
+            data: Vec::new(),
+            predicate,
+        }
+    }
+
+    pub fn from_vec(data: Vec<T>, predicate: P) -> Self {
+        let mut heap = MinMaxHeap { data, predicate };
+        for i in (0..heap.data.len() / 2).rev() {
+            heap.trickle_down(i);
+        }
+        heap
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn peek_min(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    pub fn peek_max(&self) -> Option<&T> {
+        self.max_index().map(|i| &self.data[i])
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.data.push(item);
+        self.bubble_up(self.data.len() - 1);
+    }
+
+    pub fn pop_min(&mut self) -> Option<T> {
+        self.remove(0)
+    }
+
+    pub fn pop_max(&mut self) -> Option<T> {
+        self.max_index().and_then(|i| self.remove(i))
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    fn max_index(&self) -> Option<usize> {
+        match self.data.len() {
+            0 => None,
+            1 => Some(0),
+            2 => Some(1),
+            _ if (self.predicate)(&self.data[1], &self.data[2]) => Some(2),
+            _ => Some(1),
+        }
+    }
+
+    fn remove(&mut self, i: usize) -> Option<T> {
+        if i >= self.data.len() {
+            return None;
+        }
+
+        let item = self.data.swap_remove(i);
+        if i < self.data.len() {
+            self.trickle_down(i);
+        }
+        Some(item)
+    }
+
+    fn bubble_up(&mut self, i: usize) {
+        if i == 0 {
+            return;
+        }
+
+        let parent = (i - 1) / 2;
+        let less = &self.predicate;
+        let greater = |a: &T, b: &T| less(b, a);
+
+        if is_min_level(i) {
+            if greater(&self.data[i], &self.data[parent]) {
+                self.data.swap(i, parent);
+                bubble_up_by(&mut self.data, parent, &greater);
+            } else {
+                bubble_up_by(&mut self.data, i, less);
+            }
+        } else if less(&self.data[i], &self.data[parent]) {
+            self.data.swap(i, parent);
+            bubble_up_by(&mut self.data, parent, less);
+        } else {
+            bubble_up_by(&mut self.data, i, &greater);
+        }
+    }
+
+    fn trickle_down(&mut self, i: usize) {
+        let less = &self.predicate;
+        if is_min_level(i) {
+            trickle_down_by(&mut self.data, i, less);
+        } else {
+            trickle_down_by(&mut self.data, i, &|a: &T, b: &T| less(b, a));
+        }
+    }
+}
+
+fn is_min_level(i: usize) -> bool {
+    (i + 1).ilog2() & 1 == 0
+}
+
+// `before` is `predicate` on min levels and its reverse on max levels, which
+// lets both kinds of levels share the same sifting code.
+fn bubble_up_by<T, F>(data: &mut [T], mut i: usize, before: &F)
+where
+    F: Fn(&T, &T) -> bool,
+{
+    while i > 2 {
+        let grandparent = ((i - 1) / 2 - 1) / 2;
+        if !before(&data[i], &data[grandparent]) {
+            break;
+        }
+        data.swap(i, grandparent);
+        i = grandparent;
+    }
+}
+
+fn trickle_down_by<T, F>(data: &mut [T], mut i: usize, before: &F)
+where
+    F: Fn(&T, &T) -> bool,
+{
+    let len = data.len();
+    loop {
+        let first_child = 2 * i + 1;
+        if first_child >= len {
+            break;
+        }
+
+        let first_grandchild = 4 * i + 3;
+        let mut m = first_child;
+        for j in [first_child + 1]
+            .into_iter()
+            .chain(first_grandchild..first_grandchild + 4)
+        {
+            if j < len && before(&data[j], &data[m]) {
+                m = j;
+            }
+        }
+
+        if !before(&data[m], &data[i]) {
+            break;
+        }
+        data.swap(m, i);
+        if m < first_grandchild {
+            break;
+        }
+
+        let parent = (m - 1) / 2;
+        if before(&data[parent], &data[m]) {
+            data.swap(m, parent);
+        }
+        i = m;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{random_vec, Car};
+
+    #[test]
+    fn push_and_peek() {
+        let mut heap = MinMaxHeap::new(|a: &i32, b: &i32| a < b);
+        assert_eq!(heap.peek_min(), None);
+        assert_eq!(heap.peek_max(), None);
+
+        for item in [3, 2, 4, 2, 5, 7, 1, 0, 6] {
+            heap.push(item);
+        }
+        assert_eq!(heap.len(), 9);
+        assert_eq!(heap.peek_min(), Some(&0));
+        assert_eq!(heap.peek_max(), Some(&7));
+    }
+
+    #[test]
+    fn pop_from_both_ends() {
+        let mut expected = random_vec(1000, 67);
+        expected.sort();
+
+        let mut heap = MinMaxHeap::from_vec(random_vec(1000, 67), |a, b| a < b);
+        let (mut lo, mut hi) = (0, expected.len());
+        while lo < hi {
+            if (lo + hi) % 3 == 0 {
+                hi -= 1;
+                assert_eq!(heap.pop_max(), Some(expected[hi]));
+            } else {
+                assert_eq!(heap.pop_min(), Some(expected[lo]));
+                lo += 1;
+            }
+        }
+        assert!(heap.is_empty());
+        assert_eq!(heap.pop_min(), None);
+        assert_eq!(heap.pop_max(), None);
+    }
+
+    #[test]
+    fn push_matches_from_vec() {
+        let mut expected = random_vec(1000, 71);
+        expected.sort();
+
+        let mut heap = MinMaxHeap::new(|a: &u32, b: &u32| a < b);
+        for item in random_vec(1000, 71) {
+            heap.push(item);
+        }
+
+        let mut popped = Vec::new();
+        while let Some(item) = heap.pop_max() {
+            popped.push(item);
+        }
+        popped.reverse();
+        assert_eq!(popped, expected);
+    }
+
+    #[test]
+    fn custom_predicate() {
+        let mut heap = MinMaxHeap::new(|c1: &Car, c2: &Car| c1.age > c2.age);
+        heap.push(Car { age: 25 });
+        heap.push(Car { age: 15 });
+        heap.push(Car { age: 35 });
+
+        assert_eq!(heap.pop_min(), Some(Car { age: 35 }));
+        assert_eq!(heap.pop_max(), Some(Car { age: 15 }));
+        assert_eq!(heap.into_vec(), vec![Car { age: 25 }]);
+    }
+}
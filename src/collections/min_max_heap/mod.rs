@@ -0,0 +1,3 @@
+mod min_max_heap;
+
+pub use min_max_heap::*;
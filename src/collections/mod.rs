@@ -0,0 +1,7 @@
+#![allow(clippy::module_inception)]
+
+pub mod indexed_priority_queue;
+pub mod min_max_heap;
+pub mod pairing_heap;
+pub mod priority_queue;
+pub mod top_k;
@@ -0,0 +1,3 @@
+mod pairing_heap;
+
+pub use pairing_heap::*;
@@ -0,0 +1,272 @@
+use std::mem;
+
+use crate::Order;
+
+struct Node<T> {
+    item: T,
+    children: Vec<Node<T>>,
+}
+
+// A max-heap with respect to `predicate` like `PriorityQueue`, but stored as
+// a tree so that two heaps can be melded by linking their roots.
+pub struct PairingHeap<T, P>
+where
+    P: Fn(&T, &T) -> bool,
+{
+    root: Option<Node<T>>,
+    len: usize,
+    predicate: P,
+}
+
+impl<T, P> PairingHeap<T, P>
+where
+    P: Fn(&T, &T) -> bool,
+{
+    pub fn new(predicate: P) -> Self {
+        PairingHeap {
+            root: None,
+            len: 0,
+            predicate,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|root| &root.item)
+    }
+
+    pub fn push(&mut self, item: T) {
+        let node = Node {
+            item,
+            children: Vec::new(),
+        };
+        self.meld_root(node);
+        self.len += 1;
+    }
+
+    pub fn meld(&mut self, mut other: PairingHeap<T, P>) {
+        if let Some(root) = other.root.take() {
+            self.meld_root(root);
+            self.len += other.len;
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let mut root = self.root.take()?;
+        self.len -= 1;
+
+        // Link the children in pairs from left to right, then fold the pairs
+        // into one tree from right to left.
+        let mut pairs = Vec::with_capacity(root.children.len() / 2 + 1);
+        let mut children = mem::take(&mut root.children).into_iter();
+        while let Some(first) = children.next() {
+            match children.next() {
+                Some(second) => pairs.push(link(first, second, &self.predicate)),
+                None => pairs.push(first),
+            }
+        }
+        self.root = pairs
+            .into_iter()
+            .rev()
+            .reduce(|tree, node| link(tree, node, &self.predicate));
+
+        Some(root.item)
+    }
+
+    fn meld_root(&mut self, node: Node<T>) {
+        self.root = Some(match self.root.take() {
+            Some(root) => link(root, node, &self.predicate),
+            None => node,
+        });
+    }
+}
+
+// Dropping the nodes recursively could overflow the stack, a pairing heap
+// built from sorted input is a single path.
+impl<T, P> Drop for PairingHeap<T, P>
+where
+    P: Fn(&T, &T) -> bool,
+{
+    fn drop(&mut self) {
+        let mut stack: Vec<Node<T>> = self.root.take().into_iter().collect();
+        while let Some(mut node) = stack.pop() {
+            stack.append(&mut node.children);
+        }
+    }
+}
+
+fn link<T, P>(mut a: Node<T>, mut b: Node<T>, predicate: &P) -> Node<T>
+where
+    P: Fn(&T, &T) -> bool,
+{
+    if predicate(&a.item, &b.item) {
+        b.children.push(a);
+        b
+    } else {
+        a.children.push(b);
+        a
+    }
+}
+
+pub fn sort<T: PartialOrd>(data: &mut [T], order: Order) {
+    let predicate = match order {
+        Order::Asc => |a: &T, b: &T| a < b,
+        Order::Desc => |a: &T, b: &T| a > b,
+    };
+    sort_by_predicate(data, predicate);
+}
+
+pub fn sort_by_predicate<T, P>(data: &mut [T], predicate: P)
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let len = data.len();
+
+    // The heap holds indices into `data`, so `sources[k]` ends up being the
+    // index of the element that belongs at position `k`.
+    let mut sources = vec![0; len];
+    {
+        let data = &*data;
+        let mut heap = PairingHeap::new(|a: &usize, b: &usize| predicate(&data[*a], &data[*b]));
+        for i in 0..len {
+            heap.push(i);
+        }
+        for k in (0..len).rev() {
+            sources[k] = heap.pop().unwrap();
+        }
+    }
+
+    for start in 0..len {
+        let mut k = start;
+        loop {
+            let source = mem::replace(&mut sources[k], k);
+            if source == start || source == k {
+                break;
+            }
+            data.swap(k, source);
+            k = source;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{random_vec, Car, Person};
+
+    #[test]
+    fn push_and_pop() {
+        let mut heap = PairingHeap::new(|a: &i32, b: &i32| a < b);
+        assert_eq!(heap.pop(), None);
+
+        for item in [3, 2, 4, 2, 5, 7, 1, 0, 6] {
+            heap.push(item);
+        }
+        assert_eq!(heap.len(), 9);
+        assert_eq!(heap.peek(), Some(&7));
+
+        let mut popped = Vec::new();
+        while let Some(item) = heap.pop() {
+            popped.push(item);
+        }
+        assert_eq!(popped, [7, 6, 5, 4, 3, 2, 2, 1, 0]);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn meld_heaps() {
+        let predicate = |a: &u32, b: &u32| a > b;
+        let mut first = PairingHeap::new(predicate);
+        let mut second = PairingHeap::new(predicate);
+        for item in random_vec(500, 73) {
+            first.push(item);
+        }
+        for item in random_vec(300, 79) {
+            second.push(item);
+        }
+
+        let mut expected = random_vec(500, 73);
+        expected.extend(random_vec(300, 79));
+        expected.sort();
+
+        first.meld(second);
+        assert_eq!(first.len(), 800);
+
+        let mut popped = Vec::new();
+        while let Some(item) = first.pop() {
+            popped.push(item);
+        }
+        assert_eq!(popped, expected);
+    }
+
+    #[test]
+    fn drop_long_path() {
+        let mut heap = PairingHeap::new(|a: &u32, b: &u32| a < b);
+        for item in 0..1_000_000 {
+            heap.push(item);
+        }
+        assert_eq!(heap.peek(), Some(&999_999));
+    }
+
+    #[test]
+    fn sort_array_asc() {
+        let mut arr = [2, 3, 1, 5, 4];
+        sort(&mut arr, Order::Asc);
+        assert_eq!([1, 2, 3, 4, 5], arr);
+
+        let mut arr = [
+            5, 3, 8, 2, 1, 5, 7, 2, 6, 3, 0, 2, 4, 6, 8, 9, 9, 1, 5, 4, 3, 2, 4,
+        ];
+        sort(&mut arr, Order::Asc);
+        assert_eq!(
+            [0, 1, 1, 2, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 7, 8, 8, 9, 9],
+            arr
+        );
+    }
+
+    #[test]
+    fn sort_array_desc() {
+        let mut arr = [2, 3, 1, 5, 4];
+        sort(&mut arr, Order::Desc);
+        assert_eq!([5, 4, 3, 2, 1], arr);
+    }
+
+    #[test]
+    fn sort_random_vector() {
+        let mut expected = random_vec(5000, 83);
+        expected.sort();
+
+        let mut vec = random_vec(5000, 83);
+        sort(&mut vec, Order::Asc);
+        assert_eq!(expected, vec);
+    }
+
+    #[test]
+    fn sort_struct_vector() {
+        let person1 = Person::new(25);
+        let person2 = Person::new(15);
+        let person3 = Person::new(35);
+
+        let mut vec = vec![person1, person2, person3];
+        sort(&mut vec, Order::Asc);
+        assert_eq!(vec![person2, person1, person3], vec);
+    }
+
+    #[test]
+    fn sort_struct_vector_by_predicate() {
+        let car1 = Car { age: 25 };
+        let car2 = Car { age: 15 };
+        let car3 = Car { age: 35 };
+
+        let mut vec = vec![car1, car2, car3];
+        sort_by_predicate(&mut vec, |c1, c2| c1.age < c2.age);
+        assert_eq!(vec![car2, car1, car3], vec);
+    }
+}
@@ -0,0 +1,3 @@
+mod priority_queue;
+
+pub use priority_queue::*;
@@ -0,0 +1,123 @@
+use crate::algorithms::heap_sort::heap::{
+    create_heap_by_predicate, down_heap_by_predicate, up_heap_by_predicate,
+};
+
+// A binary max-heap with respect to `predicate`, the same one heap_sort
+// builds. `pop` returns the greatest element first, so popping everything
+// yields the reverse of `into_sorted_vec`.
+pub struct PriorityQueue<T, P>
+where
+    P: Fn(&T, &T) -> bool,
+{
+    data: Vec<T>,
+    predicate: P,
+}
+
+impl<T, P> PriorityQueue<T, P>
+where
+    P: Fn(&T, &T) -> bool,
+{
+    pub fn new(predicate: P) -> Self {
+        PriorityQueue {
+            data: Vec::new(),
+            predicate,
+        }
+    }
+
+    pub fn from_vec(mut data: Vec<T>, predicate: P) -> Self {
+        create_heap_by_predicate(&mut data, &predicate);
+        PriorityQueue { data, predicate }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    pub fn push(&mut self, item: T) {
+        let i = self.data.len();
+        self.data.push(item);
+        up_heap_by_predicate(&mut self.data, i, &self.predicate);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let len = self.data.len();
+        if len == 0 {
+            return None;
+        }
+
+        self.data.swap(0, len - 1);
+        let item = self.data.pop();
+        down_heap_by_predicate(&mut self.data, 0, len - 1, &self.predicate);
+        item
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        for i in (1..self.data.len()).rev() {
+            self.data.swap(0, i);
+            down_heap_by_predicate(&mut self.data, 0, i, &self.predicate);
+        }
+        self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{random_vec, Car};
+
+    #[test]
+    fn push_and_pop() {
+        let mut queue = PriorityQueue::new(|a: &i32, b: &i32| a < b);
+        assert_eq!(queue.pop(), None);
+        assert_eq!(queue.peek(), None);
+
+        for item in [3, 2, 4, 2, 5, 7, 1, 0, 6] {
+            queue.push(item);
+        }
+        assert_eq!(queue.len(), 9);
+        assert_eq!(queue.peek(), Some(&7));
+
+        let mut popped = Vec::new();
+        while let Some(item) = queue.pop() {
+            popped.push(item);
+        }
+        assert_eq!(popped, [7, 6, 5, 4, 3, 2, 2, 1, 0]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn from_vec_into_sorted_vec() {
+        let mut expected = random_vec(1000, 53);
+        expected.sort();
+
+        let queue = PriorityQueue::from_vec(random_vec(1000, 53), |a, b| a < b);
+        assert_eq!(queue.peek(), expected.last());
+        assert_eq!(queue.into_sorted_vec(), expected);
+    }
+
+    #[test]
+    fn custom_predicate() {
+        let mut queue = PriorityQueue::new(|c1: &Car, c2: &Car| c1.age > c2.age);
+        queue.push(Car { age: 25 });
+        queue.push(Car { age: 15 });
+        queue.push(Car { age: 35 });
+
+        assert_eq!(queue.pop(), Some(Car { age: 15 }));
+        assert_eq!(
+            queue.into_sorted_vec(),
+            vec![Car { age: 35 }, Car { age: 25 }]
+        );
+    }
+}
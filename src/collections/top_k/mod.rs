@@ -0,0 +1,3 @@
+mod top_k;
+
+pub use top_k::*;
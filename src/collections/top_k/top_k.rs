@@ -0,0 +1,147 @@
+use crate::algorithms::heap_sort;
+use crate::algorithms::heap_sort::heap::{down_heap_by_predicate, up_heap_by_predicate};
+use crate::Order;
+
+// Keeps the first `k` items, in the order given by `predicate`, of everything
+// pushed into it, the same ones `partial_sort` would leave at the front. They
+// are held in a max-heap, so the root is the item next in line for eviction.
+pub struct TopK<T, P>
+where
+    P: Fn(&T, &T) -> bool,
+{
+    heap: Vec<T>,
+    k: usize,
+    predicate: P,
+}
+
+impl<T: PartialOrd> TopK<T, fn(&T, &T) -> bool> {
+    pub fn with_order(k: usize, order: Order) -> Self {
+        let predicate: fn(&T, &T) -> bool = match order {
+            Order::Asc => |a, b| a < b,
+            Order::Desc => |a, b| a > b,
+        };
+        TopK::new(k, predicate)
+    }
+}
+
+impl<T, P> TopK<T, P>
+where
+    P: Fn(&T, &T) -> bool,
+{
+    pub fn new(k: usize, predicate: P) -> Self {
+        TopK {
+            heap: Vec::with_capacity(k),
+            k,
+            predicate,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    // The last of the kept items, the one a new item has to beat once the
+    // accumulator is full.
+    pub fn peek_last(&self) -> Option<&T> {
+        self.heap.first()
+    }
+
+    pub fn push(&mut self, item: T) {
+        if self.heap.len() < self.k {
+            let i = self.heap.len();
+            self.heap.push(item);
+            up_heap_by_predicate(&mut self.heap, i, &self.predicate);
+        } else if self.k > 0 && (self.predicate)(&item, &self.heap[0]) {
+            self.heap[0] = item;
+            down_heap_by_predicate(&mut self.heap, 0, self.k, &self.predicate);
+        }
+    }
+
+    // Keeps the first `k` items of both accumulators, where `k` is the one
+    // of `self`.
+    pub fn merge(&mut self, other: TopK<T, P>) {
+        self.extend(other.heap);
+    }
+
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        heap_sort::sort_heap_by_predicate(&mut self.heap, &self.predicate);
+        self.heap
+    }
+}
+
+impl<T, P> Extend<T> for TopK<T, P>
+where
+    P: Fn(&T, &T) -> bool,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{random_vec, Car};
+
+    #[test]
+    fn top_k_with_order() {
+        let mut expected = random_vec(10_000, 113);
+        expected.sort();
+
+        let mut smallest = TopK::with_order(20, Order::Asc);
+        smallest.extend(random_vec(10_000, 113));
+        assert_eq!(smallest.len(), 20);
+        assert_eq!(smallest.peek_last(), Some(&expected[19]));
+        assert_eq!(smallest.into_sorted_vec(), expected[..20]);
+
+        let mut largest = TopK::with_order(5, Order::Desc);
+        largest.extend(random_vec(10_000, 113));
+        expected.reverse();
+        assert_eq!(largest.into_sorted_vec(), expected[..5]);
+    }
+
+    #[test]
+    fn top_k_fewer_items_than_k() {
+        let mut top = TopK::with_order(10, Order::Asc);
+        top.extend([3, 1, 2]);
+        assert_eq!(top.into_sorted_vec(), [1, 2, 3]);
+
+        let mut top = TopK::with_order(0, Order::Asc);
+        top.extend([3, 1, 2]);
+        assert!(top.is_empty());
+    }
+
+    #[test]
+    fn merge_shards() {
+        let mut expected = random_vec(3000, 127);
+        expected.extend(random_vec(2000, 131));
+        expected.sort();
+
+        let predicate = |a: &u32, b: &u32| a < b;
+        let mut first = TopK::new(50, predicate);
+        let mut second = TopK::new(50, predicate);
+        first.extend(random_vec(3000, 127));
+        second.extend(random_vec(2000, 131));
+
+        first.merge(second);
+        assert_eq!(first.into_sorted_vec(), expected[..50]);
+    }
+
+    #[test]
+    fn top_k_by_predicate() {
+        let mut top = TopK::new(2, |c1: &Car, c2: &Car| c1.age > c2.age);
+        top.extend([
+            Car { age: 25 },
+            Car { age: 15 },
+            Car { age: 35 },
+            Car { age: 5 },
+        ]);
+        assert_eq!(top.into_sorted_vec(), [Car { age: 35 }, Car { age: 25 }]);
+    }
+}
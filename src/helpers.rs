@@ -31,3 +31,15 @@ impl PartialEq for Car {
         self.age == other.age
     }
 }
+
+pub fn random_vec(len: usize, seed: u64) -> Vec<u32> {
+    let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % 1000) as u32
+        })
+        .collect()
+}
@@ -1,4 +1,6 @@
 pub mod algorithms;
+pub mod collections;
+#[cfg(test)]
 mod helpers;
 
 pub enum Order {
@@ -10,4 +12,34 @@ pub enum Partition {
     First,
     Center,
     Last,
+    MedianOfThree,
+    Ninther,
+    Random(u64),
+    MedianOfMedians,
+}
+
+#[derive(Clone, Copy)]
+pub enum Scheme {
+    Lomuto,
+    Hoare,
+    ThreeWay,
+    Block,
+}
+
+pub enum Heap {
+    Standard,
+    BottomUp,
+    Weak,
+}
+
+pub enum Arity {
+    Binary,
+    Ternary,
+    Quaternary,
+    Octonary,
+}
+
+pub enum Merge {
+    TopDown,
+    BottomUp,
 }